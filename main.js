@@ -1,7 +1,10 @@
 const ctx = new AudioContext();
 await ctx.audioWorklet.addModule('worklet.js');
+// Output 0 is the stereo pair, the others carry the extra voices.
+const voiceOutputs = 4;
 const node = new AudioWorkletNode(ctx, 'source', {
-  outputChannelCount: [2]
+  numberOfOutputs: 1 + voiceOutputs,
+  outputChannelCount: [2, ...Array(voiceOutputs).fill(1)]
 });
 node.connect(ctx.destination);
 
@@ -26,9 +29,20 @@ let buffers = { left: new Float32Array(size), right: new Float32Array(size) };
 node.port.postMessage({buffers}, [buffers.left.buffer, buffers.right.buffer]);
 
 node.port.onmessage = ev => {
-  buffers = ev.data.buffers;
+  if(ev.data.buffers)
+    buffers = ev.data.buffers;
+  else if(ev.data.tunings) {
+    const select = document.getElementById('tuning');
+    for(const name of ev.data.tunings)
+      select.append(new Option(name, name));
+    select.value = 'fourths-wide';
+  }
 }
 
+document.getElementById('tuning').addEventListener('change', ev => {
+  node.port.postMessage({tuning: ev.currentTarget.value});
+});
+
 function drawFrame() {
   if(buffers.left.byteLength > 0) {
     let dLeft = 'M ';
@@ -1,17 +1,126 @@
 /* tslint:disable */
 /* eslint-disable */
-export function process(left: Float32Array, right: Float32Array, handle: number): void;
-export function get_sample(left: Float32Array, right: Float32Array, handle: number): void;
+export function evolution_names(): string[];
+export function hamiltonian_names(): string[];
+export function output_names(): string[];
+export function interpolation_names(): string[];
+export function tuning_names(): string[];
+export function stereo_names(): string[];
+export function transport_names(): string[];
+export function unitary_names(): string[];
+export class Instance {
+  free(): void;
+  checkpoint(): void;
+  get_sample(left: Float32Array, right: Float32Array): void;
+  set_output(output: number, mode: string, value: number): void;
+  set_stereo(mode: string, value: number): void;
+  chain_depth(): number;
+  is_morphing(): boolean;
+  voice_count(): number;
+  static with_tuning(tuning: string, sample_rate: number, seed?: bigint | null): Instance;
+  clear_voices(): void;
+  set_ensemble(hamiltonian: string, unitary: string): void;
+  set_mutation(mode: string, value: number, strength: number): void;
+  static new_with_seed(sample_rate: number, seed: bigint): Instance;
+  set_evolution(mode: string): void;
+  set_frequency(freq: number): void;
+  set_transport(transport: string): void;
+  static with_partials(partials: Float32Array, sample_rate: number, seed?: bigint | null): Instance;
+  process_voices(left: Float32Array, right: Float32Array, voices: Float32Array): void;
+  set_level_rate(level: number, rate: number): void;
+  set_quadrature(on: boolean): void;
+  set_attenuation(atten: number): void;
+  set_chain_depth(depth: number): void;
+  set_unit_target(probability: number, magnitude: number): void;
+  set_level_target(level: number, probability: number, magnitude: number): void;
+  set_observer_row(output: number, row: number): void;
+  set_interpolation(mode: string): void;
+  set_smoothing_time(seconds: number): void;
+  set_variation_rate(rate: number): void;
+  process_with_params(left: Float32Array, right: Float32Array, voices: Float32Array, frequency: Float32Array, gain: Float32Array, variation_rate: Float32Array): void;
+  set_observer_column(output: number, column: number): void;
+  set_observer_motion(rate: number): void;
+  set_observer_vector(output: number, re: Float32Array, im: Float32Array): void;
+  constructor(sample_rate: number);
+  seed(): bigint;
+  morph(data: Uint8Array, seconds: number): void;
+  scrub(position: number): boolean;
+  render(seconds: number, interleaved: boolean): Float32Array;
+  process(left: Float32Array, right: Float32Array): void;
+  restore(data: Uint8Array): void;
+  position(): number | undefined;
+  set_gain(gain: number): void;
+  snapshot(): Uint8Array;
+  add_voice(channel: number, column: number): number;
+  randomize(): void;
+  set_width(width: number): void;
+}
 
 export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
 
 export interface InitOutput {
   readonly memory: WebAssembly.Memory;
-  readonly instance_new_handle: (a: number) => number;
-  readonly process: (a: number, b: number, c: any, d: number, e: number, f: any, g: number) => void;
-  readonly get_sample: (a: number, b: number, c: any, d: number, e: number, f: any, g: number) => void;
-  readonly __wbindgen_export_0: WebAssembly.Table;
+  readonly __wbg_instance_free: (a: number, b: number) => void;
+  readonly evolution_names: () => [number, number];
+  readonly hamiltonian_names: () => [number, number];
+  readonly instance_add_voice: (a: number, b: number, c: number) => number;
+  readonly instance_chain_depth: (a: number) => number;
+  readonly instance_checkpoint: (a: number) => void;
+  readonly instance_clear_voices: (a: number) => void;
+  readonly instance_get_sample: (a: number, b: number, c: number, d: any, e: number, f: number, g: any) => void;
+  readonly instance_is_morphing: (a: number) => number;
+  readonly instance_morph: (a: number, b: number, c: number, d: number) => [number, number];
+  readonly instance_new: (a: number) => [number, number, number];
+  readonly instance_new_with_seed: (a: number, b: bigint) => [number, number, number];
+  readonly instance_position: (a: number) => number;
+  readonly instance_process: (a: number, b: number, c: number, d: any, e: number, f: number, g: any) => void;
+  readonly instance_process_voices: (a: number, b: number, c: number, d: any, e: number, f: number, g: any, h: number, i: number, j: any) => void;
+  readonly instance_process_with_params: (a: number, b: number, c: number, d: any, e: number, f: number, g: any, h: number, i: number, j: any, k: number, l: number, m: number, n: number, o: number, p: number) => void;
+  readonly instance_randomize: (a: number) => void;
+  readonly instance_render: (a: number, b: number, c: number) => [number, number];
+  readonly instance_restore: (a: number, b: number, c: number) => [number, number];
+  readonly instance_scrub: (a: number, b: number) => number;
+  readonly instance_seed: (a: number) => bigint;
+  readonly instance_set_attenuation: (a: number, b: number) => void;
+  readonly instance_set_chain_depth: (a: number, b: number) => void;
+  readonly instance_set_ensemble: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly instance_set_evolution: (a: number, b: number, c: number) => [number, number];
+  readonly instance_set_frequency: (a: number, b: number) => void;
+  readonly instance_set_gain: (a: number, b: number) => void;
+  readonly instance_set_interpolation: (a: number, b: number, c: number) => [number, number];
+  readonly instance_set_level_rate: (a: number, b: number, c: number) => void;
+  readonly instance_set_level_target: (a: number, b: number, c: number, d: number) => void;
+  readonly instance_set_mutation: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly instance_set_observer_column: (a: number, b: number, c: number) => void;
+  readonly instance_set_observer_motion: (a: number, b: number) => void;
+  readonly instance_set_observer_row: (a: number, b: number, c: number) => void;
+  readonly instance_set_observer_vector: (a: number, b: number, c: number, d: number, e: number, f: number) => void;
+  readonly instance_set_output: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly instance_set_quadrature: (a: number, b: number) => void;
+  readonly instance_set_smoothing_time: (a: number, b: number) => void;
+  readonly instance_set_stereo: (a: number, b: number, c: number, d: number) => [number, number];
+  readonly instance_set_transport: (a: number, b: number, c: number) => [number, number];
+  readonly instance_set_unit_target: (a: number, b: number, c: number) => void;
+  readonly instance_set_variation_rate: (a: number, b: number) => void;
+  readonly instance_set_width: (a: number, b: number) => void;
+  readonly instance_snapshot: (a: number) => [number, number];
+  readonly instance_voice_count: (a: number) => number;
+  readonly instance_with_partials: (a: number, b: number, c: number, d: number, e: bigint) => [number, number, number];
+  readonly instance_with_tuning: (a: number, b: number, c: number, d: number, e: bigint) => [number, number, number];
+  readonly interpolation_names: () => [number, number];
+  readonly output_names: () => [number, number];
+  readonly stereo_names: () => [number, number];
+  readonly transport_names: () => [number, number];
+  readonly tuning_names: () => [number, number];
+  readonly unitary_names: () => [number, number];
+  readonly __wbindgen_exn_store: (a: number) => void;
+  readonly __externref_table_alloc: () => number;
+  readonly __wbindgen_export_2: WebAssembly.Table;
   readonly __wbindgen_malloc: (a: number, b: number) => number;
+  readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
+  readonly __externref_table_dealloc: (a: number) => void;
+  readonly __wbindgen_free: (a: number, b: number, c: number) => void;
+  readonly __externref_drop_slice: (a: number, b: number) => void;
   readonly __wbindgen_start: () => void;
 }
 
@@ -14,6 +14,30 @@ function getArrayU8FromWasm0(ptr, len) {
     return getUint8ArrayMemory0().subarray(ptr / 1, ptr / 1 + len);
 }
 
+function addToExternrefTable0(obj) {
+    const idx = wasm.__externref_table_alloc();
+    wasm.__wbindgen_export_2.set(idx, obj);
+    return idx;
+}
+
+function handleError(f, args) {
+    try {
+        return f.apply(this, args);
+    } catch (e) {
+        const idx = addToExternrefTable0(e);
+        wasm.__wbindgen_exn_store(idx);
+    }
+}
+
+const cachedTextDecoder = (typeof TextDecoder !== 'undefined' ? new TextDecoder('utf-8', { ignoreBOM: true, fatal: true }) : { decode: () => { throw Error('TextDecoder not available') } } );
+
+if (typeof TextDecoder !== 'undefined') { cachedTextDecoder.decode(); };
+
+function getStringFromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return cachedTextDecoder.decode(getUint8ArrayMemory0().subarray(ptr, ptr + len));
+}
+
 let cachedFloat32ArrayMemory0 = null;
 
 function getFloat32ArrayMemory0() {
@@ -31,30 +55,180 @@ function passArrayF32ToWasm0(arg, malloc) {
     WASM_VECTOR_LEN = arg.length;
     return ptr;
 }
+
+const cachedTextEncoder = (typeof TextEncoder !== 'undefined' ? new TextEncoder('utf-8') : { encode: () => { throw Error('TextEncoder not available') } } );
+
+const encodeString = (typeof cachedTextEncoder.encodeInto === 'function'
+    ? function (arg, view) {
+    return cachedTextEncoder.encodeInto(arg, view);
+}
+    : function (arg, view) {
+    const buf = cachedTextEncoder.encode(arg);
+    view.set(buf);
+    return {
+        read: arg.length,
+        written: buf.length
+    };
+});
+
+function passStringToWasm0(arg, malloc, realloc) {
+
+    if (realloc === undefined) {
+        const buf = cachedTextEncoder.encode(arg);
+        const ptr = malloc(buf.length, 1) >>> 0;
+        getUint8ArrayMemory0().subarray(ptr, ptr + buf.length).set(buf);
+        WASM_VECTOR_LEN = buf.length;
+        return ptr;
+    }
+
+    let len = arg.length;
+    let ptr = malloc(len, 1) >>> 0;
+
+    const mem = getUint8ArrayMemory0();
+
+    let offset = 0;
+
+    for (; offset < len; offset++) {
+        const code = arg.charCodeAt(offset);
+        if (code > 0x7F) break;
+        mem[ptr + offset] = code;
+    }
+
+    if (offset !== len) {
+        if (offset !== 0) {
+            arg = arg.slice(offset);
+        }
+        ptr = realloc(ptr, len, len = offset + arg.length * 3, 1) >>> 0;
+        const view = getUint8ArrayMemory0().subarray(ptr + offset, ptr + len);
+        const ret = encodeString(arg, view);
+
+        offset += ret.written;
+        ptr = realloc(ptr, len, offset, 1) >>> 0;
+    }
+
+    WASM_VECTOR_LEN = offset;
+    return ptr;
+}
+
+function takeFromExternrefTable0(idx) {
+    const value = wasm.__wbindgen_export_2.get(idx);
+    wasm.__externref_table_dealloc(idx);
+    return value;
+}
+
+function isLikeNone(x) {
+    return x === undefined || x === null;
+}
+
+function passArray8ToWasm0(arg, malloc) {
+    const ptr = malloc(arg.length * 1, 1) >>> 0;
+    getUint8ArrayMemory0().set(arg, ptr / 1);
+    WASM_VECTOR_LEN = arg.length;
+    return ptr;
+}
+
+function getArrayF32FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getFloat32ArrayMemory0().subarray(ptr / 4, ptr / 4 + len);
+}
+
+let cachedDataViewMemory0 = null;
+
+function getDataViewMemory0() {
+    if (cachedDataViewMemory0 === null || cachedDataViewMemory0.buffer.detached === true || (cachedDataViewMemory0.buffer.detached === undefined && cachedDataViewMemory0.buffer !== wasm.memory.buffer)) {
+        cachedDataViewMemory0 = new DataView(wasm.memory.buffer);
+    }
+    return cachedDataViewMemory0;
+}
+
+function getArrayJsValueFromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    const mem = getDataViewMemory0();
+    const result = [];
+    for (let i = ptr; i < ptr + 4 * len; i += 4) {
+        result.push(wasm.__wbindgen_export_2.get(mem.getUint32(i, true)));
+    }
+    wasm.__externref_drop_slice(ptr, len);
+    return result;
+}
+/**
+ * @returns {string[]}
+ */
+export function evolution_names() {
+    const ret = wasm.evolution_names();
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+}
+
+/**
+ * @returns {string[]}
+ */
+export function hamiltonian_names() {
+    const ret = wasm.hamiltonian_names();
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+}
+
 /**
- * @param {Float32Array} left
- * @param {Float32Array} right
- * @param {number} handle
+ * @returns {string[]}
  */
-export function process(left, right, handle) {
-    var ptr0 = passArrayF32ToWasm0(left, wasm.__wbindgen_malloc);
-    var len0 = WASM_VECTOR_LEN;
-    var ptr1 = passArrayF32ToWasm0(right, wasm.__wbindgen_malloc);
-    var len1 = WASM_VECTOR_LEN;
-    wasm.process(ptr0, len0, left, ptr1, len1, right, handle);
+export function output_names() {
+    const ret = wasm.output_names();
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
 }
 
 /**
- * @param {Float32Array} left
- * @param {Float32Array} right
- * @param {number} handle
+ * @returns {string[]}
  */
-export function get_sample(left, right, handle) {
-    var ptr0 = passArrayF32ToWasm0(left, wasm.__wbindgen_malloc);
-    var len0 = WASM_VECTOR_LEN;
-    var ptr1 = passArrayF32ToWasm0(right, wasm.__wbindgen_malloc);
-    var len1 = WASM_VECTOR_LEN;
-    wasm.get_sample(ptr0, len0, left, ptr1, len1, right, handle);
+export function interpolation_names() {
+    const ret = wasm.interpolation_names();
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+}
+
+/**
+ * @returns {string[]}
+ */
+export function tuning_names() {
+    const ret = wasm.tuning_names();
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+}
+
+/**
+ * @returns {string[]}
+ */
+export function stereo_names() {
+    const ret = wasm.stereo_names();
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+}
+
+/**
+ * @returns {string[]}
+ */
+export function transport_names() {
+    const ret = wasm.transport_names();
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+}
+
+/**
+ * @returns {string[]}
+ */
+export function unitary_names() {
+    const ret = wasm.unitary_names();
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
 }
 
 const InstanceFinalization = (typeof FinalizationRegistry === 'undefined')
@@ -63,6 +237,14 @@ const InstanceFinalization = (typeof FinalizationRegistry === 'undefined')
 
 export class Instance {
 
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(Instance.prototype);
+        obj.__wbg_ptr = ptr;
+        InstanceFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+
     __destroy_into_raw() {
         const ptr = this.__wbg_ptr;
         this.__wbg_ptr = 0;
@@ -74,14 +256,410 @@ export class Instance {
         const ptr = this.__destroy_into_raw();
         wasm.__wbg_instance_free(ptr, 0);
     }
+    checkpoint() {
+        wasm.instance_checkpoint(this.__wbg_ptr);
+    }
+    /**
+     * @param {Float32Array} left
+     * @param {Float32Array} right
+     */
+    get_sample(left, right) {
+        var ptr0 = passArrayF32ToWasm0(left, wasm.__wbindgen_malloc);
+        var len0 = WASM_VECTOR_LEN;
+        var ptr1 = passArrayF32ToWasm0(right, wasm.__wbindgen_malloc);
+        var len1 = WASM_VECTOR_LEN;
+        wasm.instance_get_sample(this.__wbg_ptr, ptr0, len0, left, ptr1, len1, right);
+    }
+    /**
+     * @param {number} output
+     * @param {string} mode
+     * @param {number} value
+     */
+    set_output(output, mode, value) {
+        const ptr0 = passStringToWasm0(mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.instance_set_output(this.__wbg_ptr, output, ptr0, len0, value);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {string} mode
+     * @param {number} value
+     */
+    set_stereo(mode, value) {
+        const ptr0 = passStringToWasm0(mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.instance_set_stereo(this.__wbg_ptr, ptr0, len0, value);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @returns {number}
+     */
+    chain_depth() {
+        const ret = wasm.instance_chain_depth(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {boolean}
+     */
+    is_morphing() {
+        const ret = wasm.instance_is_morphing(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {number}
+     */
+    voice_count() {
+        const ret = wasm.instance_voice_count(this.__wbg_ptr);
+        return ret >>> 0;
+    }
     /**
+     * @param {string} tuning
      * @param {number} sample_rate
+     * @param {bigint | null} [seed]
+     * @returns {Instance}
+     */
+    static with_tuning(tuning, sample_rate, seed) {
+        const ptr0 = passStringToWasm0(tuning, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.instance_with_tuning(ptr0, len0, sample_rate, !isLikeNone(seed), isLikeNone(seed) ? BigInt(0) : seed);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return Instance.__wrap(ret[0]);
+    }
+    clear_voices() {
+        wasm.instance_clear_voices(this.__wbg_ptr);
+    }
+    /**
+     * @param {string} hamiltonian
+     * @param {string} unitary
+     */
+    set_ensemble(hamiltonian, unitary) {
+        const ptr0 = passStringToWasm0(hamiltonian, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(unitary, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.instance_set_ensemble(this.__wbg_ptr, ptr0, len0, ptr1, len1);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {string} mode
+     * @param {number} value
+     * @param {number} strength
+     */
+    set_mutation(mode, value, strength) {
+        const ptr0 = passStringToWasm0(mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.instance_set_mutation(this.__wbg_ptr, ptr0, len0, value, strength);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} sample_rate
+     * @param {bigint} seed
+     * @returns {Instance}
+     */
+    static new_with_seed(sample_rate, seed) {
+        const ret = wasm.instance_new_with_seed(sample_rate, seed);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return Instance.__wrap(ret[0]);
+    }
+    /**
+     * @param {string} mode
+     */
+    set_evolution(mode) {
+        const ptr0 = passStringToWasm0(mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.instance_set_evolution(this.__wbg_ptr, ptr0, len0);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} freq
+     */
+    set_frequency(freq) {
+        wasm.instance_set_frequency(this.__wbg_ptr, freq);
+    }
+    /**
+     * @param {string} transport
+     */
+    set_transport(transport) {
+        const ptr0 = passStringToWasm0(transport, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.instance_set_transport(this.__wbg_ptr, ptr0, len0);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {Float32Array} partials
+     * @param {number} sample_rate
+     * @param {bigint | null} [seed]
+     * @returns {Instance}
+     */
+    static with_partials(partials, sample_rate, seed) {
+        const ptr0 = passArrayF32ToWasm0(partials, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.instance_with_partials(ptr0, len0, sample_rate, !isLikeNone(seed), isLikeNone(seed) ? BigInt(0) : seed);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return Instance.__wrap(ret[0]);
+    }
+    /**
+     * @param {Float32Array} left
+     * @param {Float32Array} right
+     * @param {Float32Array} voices
+     */
+    process_voices(left, right, voices) {
+        var ptr0 = passArrayF32ToWasm0(left, wasm.__wbindgen_malloc);
+        var len0 = WASM_VECTOR_LEN;
+        var ptr1 = passArrayF32ToWasm0(right, wasm.__wbindgen_malloc);
+        var len1 = WASM_VECTOR_LEN;
+        var ptr2 = passArrayF32ToWasm0(voices, wasm.__wbindgen_malloc);
+        var len2 = WASM_VECTOR_LEN;
+        wasm.instance_process_voices(this.__wbg_ptr, ptr0, len0, left, ptr1, len1, right, ptr2, len2, voices);
+    }
+    /**
+     * @param {number} level
+     * @param {number} rate
+     */
+    set_level_rate(level, rate) {
+        wasm.instance_set_level_rate(this.__wbg_ptr, level, rate);
+    }
+    /**
+     * @param {boolean} on
+     */
+    set_quadrature(on) {
+        wasm.instance_set_quadrature(this.__wbg_ptr, on);
+    }
+    /**
+     * @param {number} atten
+     */
+    set_attenuation(atten) {
+        wasm.instance_set_attenuation(this.__wbg_ptr, atten);
+    }
+    /**
+     * @param {number} depth
+     */
+    set_chain_depth(depth) {
+        wasm.instance_set_chain_depth(this.__wbg_ptr, depth);
+    }
+    /**
+     * @param {number} probability
+     * @param {number} magnitude
+     */
+    set_unit_target(probability, magnitude) {
+        wasm.instance_set_unit_target(this.__wbg_ptr, probability, magnitude);
+    }
+    /**
+     * @param {number} level
+     * @param {number} probability
+     * @param {number} magnitude
+     */
+    set_level_target(level, probability, magnitude) {
+        wasm.instance_set_level_target(this.__wbg_ptr, level, probability, magnitude);
+    }
+    /**
+     * @param {number} output
+     * @param {number} row
+     */
+    set_observer_row(output, row) {
+        wasm.instance_set_observer_row(this.__wbg_ptr, output, row);
+    }
+    /**
+     * @param {string} mode
+     */
+    set_interpolation(mode) {
+        const ptr0 = passStringToWasm0(mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.instance_set_interpolation(this.__wbg_ptr, ptr0, len0);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} seconds
+     */
+    set_smoothing_time(seconds) {
+        wasm.instance_set_smoothing_time(this.__wbg_ptr, seconds);
+    }
+    /**
+     * @param {number} rate
+     */
+    set_variation_rate(rate) {
+        wasm.instance_set_variation_rate(this.__wbg_ptr, rate);
+    }
+    /**
+     * @param {Float32Array} left
+     * @param {Float32Array} right
+     * @param {Float32Array} voices
+     * @param {Float32Array} frequency
+     * @param {Float32Array} gain
+     * @param {Float32Array} variation_rate
+     */
+    process_with_params(left, right, voices, frequency, gain, variation_rate) {
+        var ptr0 = passArrayF32ToWasm0(left, wasm.__wbindgen_malloc);
+        var len0 = WASM_VECTOR_LEN;
+        var ptr1 = passArrayF32ToWasm0(right, wasm.__wbindgen_malloc);
+        var len1 = WASM_VECTOR_LEN;
+        var ptr2 = passArrayF32ToWasm0(voices, wasm.__wbindgen_malloc);
+        var len2 = WASM_VECTOR_LEN;
+        const ptr3 = passArrayF32ToWasm0(frequency, wasm.__wbindgen_malloc);
+        const len3 = WASM_VECTOR_LEN;
+        const ptr4 = passArrayF32ToWasm0(gain, wasm.__wbindgen_malloc);
+        const len4 = WASM_VECTOR_LEN;
+        const ptr5 = passArrayF32ToWasm0(variation_rate, wasm.__wbindgen_malloc);
+        const len5 = WASM_VECTOR_LEN;
+        wasm.instance_process_with_params(this.__wbg_ptr, ptr0, len0, left, ptr1, len1, right, ptr2, len2, voices, ptr3, len3, ptr4, len4, ptr5, len5);
+    }
+    /**
+     * @param {number} output
+     * @param {number} column
+     */
+    set_observer_column(output, column) {
+        wasm.instance_set_observer_column(this.__wbg_ptr, output, column);
+    }
+    /**
+     * @param {number} rate
+     */
+    set_observer_motion(rate) {
+        wasm.instance_set_observer_motion(this.__wbg_ptr, rate);
+    }
+    /**
+     * @param {number} output
+     * @param {Float32Array} re
+     * @param {Float32Array} im
+     */
+    set_observer_vector(output, re, im) {
+        const ptr0 = passArrayF32ToWasm0(re, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passArrayF32ToWasm0(im, wasm.__wbindgen_malloc);
+        const len1 = WASM_VECTOR_LEN;
+        wasm.instance_set_observer_vector(this.__wbg_ptr, output, ptr0, len0, ptr1, len1);
+    }
+    /**
+     * @param {number} sample_rate
+     */
+    constructor(sample_rate) {
+        const ret = wasm.instance_new(sample_rate);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        this.__wbg_ptr = ret[0] >>> 0;
+        InstanceFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * @returns {bigint}
+     */
+    seed() {
+        const ret = wasm.instance_seed(this.__wbg_ptr);
+        return BigInt.asUintN(64, ret);
+    }
+    /**
+     * @param {Uint8Array} data
+     * @param {number} seconds
+     */
+    morph(data, seconds) {
+        const ptr0 = passArray8ToWasm0(data, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.instance_morph(this.__wbg_ptr, ptr0, len0, seconds);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @param {number} position
+     * @returns {boolean}
+     */
+    scrub(position) {
+        const ret = wasm.instance_scrub(this.__wbg_ptr, position);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} seconds
+     * @param {boolean} interleaved
+     * @returns {Float32Array}
+     */
+    render(seconds, interleaved) {
+        const ret = wasm.instance_render(this.__wbg_ptr, seconds, interleaved);
+        var v1 = getArrayF32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * @param {Float32Array} left
+     * @param {Float32Array} right
+     */
+    process(left, right) {
+        var ptr0 = passArrayF32ToWasm0(left, wasm.__wbindgen_malloc);
+        var len0 = WASM_VECTOR_LEN;
+        var ptr1 = passArrayF32ToWasm0(right, wasm.__wbindgen_malloc);
+        var len1 = WASM_VECTOR_LEN;
+        wasm.instance_process(this.__wbg_ptr, ptr0, len0, left, ptr1, len1, right);
+    }
+    /**
+     * @param {Uint8Array} data
+     */
+    restore(data) {
+        const ptr0 = passArray8ToWasm0(data, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.instance_restore(this.__wbg_ptr, ptr0, len0);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @returns {number | undefined}
+     */
+    position() {
+        const ret = wasm.instance_position(this.__wbg_ptr);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @param {number} gain
+     */
+    set_gain(gain) {
+        wasm.instance_set_gain(this.__wbg_ptr, gain);
+    }
+    /**
+     * @returns {Uint8Array}
+     */
+    snapshot() {
+        const ret = wasm.instance_snapshot(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * @param {number} channel
+     * @param {number} column
      * @returns {number}
      */
-    static new_handle(sample_rate) {
-        const ret = wasm.instance_new_handle(sample_rate);
+    add_voice(channel, column) {
+        const ret = wasm.instance_add_voice(this.__wbg_ptr, channel, column);
         return ret >>> 0;
     }
+    randomize() {
+        wasm.instance_randomize(this.__wbg_ptr);
+    }
+    /**
+     * @param {number} width
+     */
+    set_width(width) {
+        wasm.instance_set_width(this.__wbg_ptr, width);
+    }
 }
 
 async function __wbg_load(module, imports) {
@@ -118,15 +696,18 @@ async function __wbg_load(module, imports) {
 function __wbg_get_imports() {
     const imports = {};
     imports.wbg = {};
-    imports.wbg.__wbg_random_47a3bb4a3162d96b = function() {
-        const ret = Math.random();
-        return ret;
-    };
+    imports.wbg.__wbg_getRandomValues_3c9c0d586e575a16 = function() { return handleError(function (arg0, arg1) {
+        globalThis.crypto.getRandomValues(getArrayU8FromWasm0(arg0, arg1));
+    }, arguments) };
     imports.wbg.__wbindgen_copy_to_typed_array = function(arg0, arg1, arg2) {
         new Uint8Array(arg2.buffer, arg2.byteOffset, arg2.byteLength).set(getArrayU8FromWasm0(arg0, arg1));
     };
+    imports.wbg.__wbindgen_error_new = function(arg0, arg1) {
+        const ret = new Error(getStringFromWasm0(arg0, arg1));
+        return ret;
+    };
     imports.wbg.__wbindgen_init_externref_table = function() {
-        const table = wasm.__wbindgen_export_0;
+        const table = wasm.__wbindgen_export_2;
         const offset = table.grow(4);
         table.set(0, undefined);
         table.set(offset + 0, undefined);
@@ -135,6 +716,13 @@ function __wbg_get_imports() {
         table.set(offset + 3, false);
         ;
     };
+    imports.wbg.__wbindgen_string_new = function(arg0, arg1) {
+        const ret = getStringFromWasm0(arg0, arg1);
+        return ret;
+    };
+    imports.wbg.__wbindgen_throw = function(arg0, arg1) {
+        throw new Error(getStringFromWasm0(arg0, arg1));
+    };
 
     return imports;
 }
@@ -146,6 +734,7 @@ function __wbg_init_memory(imports, memory) {
 function __wbg_finalize_init(instance, module) {
     wasm = instance.exports;
     __wbg_init.__wbindgen_wasm_module = module;
+    cachedDataViewMemory0 = null;
     cachedFloat32ArrayMemory0 = null;
     cachedUint8ArrayMemory0 = null;
 
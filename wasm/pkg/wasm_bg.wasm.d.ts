@@ -1,9 +1,65 @@
 /* tslint:disable */
 /* eslint-disable */
 export const memory: WebAssembly.Memory;
-export const instance_new_handle: (a: number) => number;
-export const process: (a: number, b: number, c: any, d: number, e: number, f: any, g: number) => void;
-export const get_sample: (a: number, b: number, c: any, d: number, e: number, f: any, g: number) => void;
-export const __wbindgen_export_0: WebAssembly.Table;
+export const __wbg_instance_free: (a: number, b: number) => void;
+export const evolution_names: () => [number, number];
+export const hamiltonian_names: () => [number, number];
+export const instance_add_voice: (a: number, b: number, c: number) => number;
+export const instance_chain_depth: (a: number) => number;
+export const instance_checkpoint: (a: number) => void;
+export const instance_clear_voices: (a: number) => void;
+export const instance_get_sample: (a: number, b: number, c: number, d: any, e: number, f: number, g: any) => void;
+export const instance_is_morphing: (a: number) => number;
+export const instance_morph: (a: number, b: number, c: number, d: number) => [number, number];
+export const instance_new: (a: number) => [number, number, number];
+export const instance_new_with_seed: (a: number, b: bigint) => [number, number, number];
+export const instance_position: (a: number) => number;
+export const instance_process: (a: number, b: number, c: number, d: any, e: number, f: number, g: any) => void;
+export const instance_process_voices: (a: number, b: number, c: number, d: any, e: number, f: number, g: any, h: number, i: number, j: any) => void;
+export const instance_process_with_params: (a: number, b: number, c: number, d: any, e: number, f: number, g: any, h: number, i: number, j: any, k: number, l: number, m: number, n: number, o: number, p: number) => void;
+export const instance_randomize: (a: number) => void;
+export const instance_render: (a: number, b: number, c: number) => [number, number];
+export const instance_restore: (a: number, b: number, c: number) => [number, number];
+export const instance_scrub: (a: number, b: number) => number;
+export const instance_seed: (a: number) => bigint;
+export const instance_set_attenuation: (a: number, b: number) => void;
+export const instance_set_chain_depth: (a: number, b: number) => void;
+export const instance_set_ensemble: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const instance_set_evolution: (a: number, b: number, c: number) => [number, number];
+export const instance_set_frequency: (a: number, b: number) => void;
+export const instance_set_gain: (a: number, b: number) => void;
+export const instance_set_interpolation: (a: number, b: number, c: number) => [number, number];
+export const instance_set_level_rate: (a: number, b: number, c: number) => void;
+export const instance_set_level_target: (a: number, b: number, c: number, d: number) => void;
+export const instance_set_mutation: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const instance_set_observer_column: (a: number, b: number, c: number) => void;
+export const instance_set_observer_motion: (a: number, b: number) => void;
+export const instance_set_observer_row: (a: number, b: number, c: number) => void;
+export const instance_set_observer_vector: (a: number, b: number, c: number, d: number, e: number, f: number) => void;
+export const instance_set_output: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const instance_set_quadrature: (a: number, b: number) => void;
+export const instance_set_smoothing_time: (a: number, b: number) => void;
+export const instance_set_stereo: (a: number, b: number, c: number, d: number) => [number, number];
+export const instance_set_transport: (a: number, b: number, c: number) => [number, number];
+export const instance_set_unit_target: (a: number, b: number, c: number) => void;
+export const instance_set_variation_rate: (a: number, b: number) => void;
+export const instance_set_width: (a: number, b: number) => void;
+export const instance_snapshot: (a: number) => [number, number];
+export const instance_voice_count: (a: number) => number;
+export const instance_with_partials: (a: number, b: number, c: number, d: number, e: bigint) => [number, number, number];
+export const instance_with_tuning: (a: number, b: number, c: number, d: number, e: bigint) => [number, number, number];
+export const interpolation_names: () => [number, number];
+export const output_names: () => [number, number];
+export const stereo_names: () => [number, number];
+export const transport_names: () => [number, number];
+export const tuning_names: () => [number, number];
+export const unitary_names: () => [number, number];
+export const __wbindgen_exn_store: (a: number) => void;
+export const __externref_table_alloc: () => number;
+export const __wbindgen_export_2: WebAssembly.Table;
 export const __wbindgen_malloc: (a: number, b: number) => number;
+export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
+export const __externref_table_dealloc: (a: number) => void;
+export const __wbindgen_free: (a: number, b: number, c: number) => void;
+export const __externref_drop_slice: (a: number, b: number) => void;
 export const __wbindgen_start: () => void;
@@ -0,0 +1,236 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::process::ExitCode;
+
+use wasm::{Instance, Tuning};
+
+const USAGE: &str = "\
+Usage: wavescapes-render [options] <output.wav>
+
+Options:
+  -d, --duration <seconds>     length of the output (default 60)
+  -s, --seed <number>          RNG seed (default random)
+  -r, --sample-rate <hz>       sample rate (default 48000)
+  -t, --tuning <name>          tuning preset
+  -p, --partials <r1,r2,...>   explicit partial ratios, overrides --tuning
+  -f, --format <format>        pcm16, pcm24 or float32 (default pcm16)
+      --frequency <hz>         base frequency
+      --variation-rate <rate>  speed of the evolution
+      --attenuation <tilt>     spectral tilt
+      --gain <gain>            output gain";
+
+const CHUNK: usize = 4096;
+
+#[derive(Clone, Copy)]
+enum Format {
+    Pcm16,
+    Pcm24,
+    Float32,
+}
+
+struct Options {
+    output: String,
+    duration: f32,
+    seed: Option<u64>,
+    sample_rate: u32,
+    partials: Vec<f32>,
+    format: Format,
+    frequency: Option<f32>,
+    variation_rate: Option<f32>,
+    attenuation: Option<f32>,
+    gain: Option<f32>,
+}
+
+fn main() -> ExitCode {
+    let opts = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(opts)) => opts,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match render(&opts) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}: {err}", opts.output);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// None if help was asked for.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut opts = Options {
+        output: String::new(),
+        duration: 60.0,
+        seed: None,
+        sample_rate: 48000,
+        partials: Tuning::default().partials(),
+        format: Format::Pcm16,
+        frequency: None,
+        variation_rate: None,
+        attenuation: None,
+        gain: None,
+    };
+    let mut partials = None;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("missing value for {arg}"));
+        match arg.as_str() {
+            "-d" | "--duration" => opts.duration = parse(&value()?)?,
+            "-s" | "--seed" => opts.seed = Some(parse(&value()?)?),
+            "-r" | "--sample-rate" => opts.sample_rate = parse(&value()?)?,
+            "-t" | "--tuning" => {
+                let name = value()?;
+                let tuning = Tuning::from_name(&name)
+                    .ok_or_else(|| format!("unknown tuning: {name}"))?;
+                opts.partials = tuning.partials();
+            },
+            "-p" | "--partials" => partials = Some(value()?
+                .split(',')
+                .map(parse)
+                .collect::<Result<Vec<f32>, _>>()?),
+            "-f" | "--format" => opts.format = match value()?.as_str() {
+                "pcm16" => Format::Pcm16,
+                "pcm24" => Format::Pcm24,
+                "float32" => Format::Float32,
+                other => return Err(format!("unknown format: {other}")),
+            },
+            "--frequency" => opts.frequency = Some(parse(&value()?)?),
+            "--variation-rate" => opts.variation_rate = Some(parse(&value()?)?),
+            "--attenuation" => opts.attenuation = Some(parse(&value()?)?),
+            "--gain" => opts.gain = Some(parse(&value()?)?),
+            "-h" | "--help" => return Ok(None),
+            _ if arg.starts_with('-') => return Err(format!("unknown option: {arg}")),
+            _ if opts.output.is_empty() => opts.output = arg,
+            _ => return Err(format!("unexpected argument: {arg}")),
+        }
+    }
+    if let Some(partials) = partials {
+        if partials.is_empty() {
+            return Err("empty partial list".to_owned());
+        }
+        opts.partials = partials;
+    }
+    if opts.output.is_empty() {
+        return Err("no output file given".to_owned());
+    }
+    if opts.sample_rate == 0 {
+        return Err("sample rate must be positive".to_owned());
+    }
+    Ok(Some(opts))
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value.trim().parse().map_err(|_| format!("invalid value: {value}"))
+}
+
+fn render(opts: &Options) -> std::io::Result<()> {
+    let mut inst = Instance::with_partials(&opts.partials, opts.sample_rate as f32, opts.seed)
+        .map_err(std::io::Error::other)?;
+    eprintln!("seed: {}", inst.seed());
+    // Set without smoothing, these are the starting values.
+    inst.set_smoothing_time(0.0);
+    if let Some(freq) = opts.frequency {
+        inst.set_frequency(freq);
+    }
+    if let Some(rate) = opts.variation_rate {
+        inst.set_variation_rate(rate);
+    }
+    if let Some(atten) = opts.attenuation {
+        inst.set_attenuation(atten);
+    }
+    if let Some(gain) = opts.gain {
+        inst.set_gain(gain);
+    }
+
+    let frames = (opts.duration.max(0.0) * opts.sample_rate as f32) as usize;
+    let mut out = BufWriter::new(File::create(&opts.output)?);
+    write_header(&mut out, opts.format, opts.sample_rate, frames)?;
+    let mut left = vec![0.0; CHUNK];
+    let mut right = vec![0.0; CHUNK];
+    let mut remaining = frames;
+    while remaining > 0 {
+        let len = remaining.min(CHUNK);
+        inst.process(&mut left[..len], &mut right[..len]);
+        for (&l, &r) in left[..len].iter().zip(&right[..len]) {
+            write_sample(&mut out, opts.format, l)?;
+            write_sample(&mut out, opts.format, r)?;
+        }
+        remaining -= len;
+    }
+    out.flush()
+}
+
+fn write_header(out: &mut impl Write, format: Format, sample_rate: u32, frames: usize) -> std::io::Result<()> {
+    const CHANNELS: u16 = 2;
+    let (tag, bits): (u16, u16) = match format {
+        Format::Pcm16 => (1, 16),
+        Format::Pcm24 => (1, 24),
+        Format::Float32 => (3, 32),
+    };
+    let block_align = CHANNELS * bits / 8;
+    let too_long = || std::io::Error::other("output too long for a WAV file");
+    let data_len = frames.checked_mul(block_align as usize)
+        .and_then(|len| u32::try_from(len).ok())
+        .ok_or_else(too_long)?;
+    let riff_len = data_len.checked_add(36).ok_or_else(too_long)?;
+    let byte_rate = sample_rate.checked_mul(block_align as u32)
+        .ok_or_else(|| std::io::Error::other("sample rate too high for a WAV file"))?;
+    out.write_all(b"RIFF")?;
+    out.write_all(&riff_len.to_le_bytes())?;
+    out.write_all(b"WAVE")?;
+    out.write_all(b"fmt ")?;
+    out.write_all(&16u32.to_le_bytes())?;
+    out.write_all(&tag.to_le_bytes())?;
+    out.write_all(&CHANNELS.to_le_bytes())?;
+    out.write_all(&sample_rate.to_le_bytes())?;
+    out.write_all(&byte_rate.to_le_bytes())?;
+    out.write_all(&block_align.to_le_bytes())?;
+    out.write_all(&bits.to_le_bytes())?;
+    out.write_all(b"data")?;
+    out.write_all(&data_len.to_le_bytes())
+}
+
+fn write_sample(out: &mut impl Write, format: Format, x: f32) -> std::io::Result<()> {
+    let x = x.clamp(-1.0, 1.0);
+    match format {
+        Format::Pcm16 => out.write_all(&((x * 32767.0).round() as i16).to_le_bytes()),
+        Format::Pcm24 => out.write_all(&((x * 8388607.0).round() as i32).to_le_bytes()[..3]),
+        Format::Float32 => out.write_all(&x.to_le_bytes()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_with(name: &str, extra: &[&str]) -> Vec<u8> {
+        let path = std::env::temp_dir().join(format!("wavescapes-render-{}-{name}.wav", std::process::id()));
+        let path = path.to_str().unwrap().to_owned();
+        let args = ["-d", "0.2", "-s", "4"].iter().chain(extra).map(|&a| a.to_owned()).chain([path.clone()]);
+        let opts = parse_args(args).unwrap().unwrap();
+        render(&opts).unwrap();
+        let data = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        data
+    }
+
+    #[test]
+    fn options_change_output() {
+        let base = render_with("base", &[]);
+        assert_eq!(base, render_with("again", &[]));
+        for (name, option) in [("frequency", ["--frequency", "440"]), ("attenuation", ["--attenuation", "2"]),
+                ("gain", ["--gain", "0.5"]), ("rate", ["--variation-rate", "5"])] {
+            assert_ne!(base, render_with(name, &option), "{name} has no effect");
+        }
+    }
+
+    #[test]
+    fn help_is_not_an_error() {
+        assert!(matches!(parse_args(["--help".to_owned()].into_iter()), Ok(None)));
+    }
+}
@@ -0,0 +1,259 @@
+use nalgebra::Complex;
+use wasm_bindgen::prelude::*;
+
+use crate::{Evolution, Hamiltonian, Interpolation, Mutation, Observer, Output, Stereo, Target, Transport, Tuning, Unitary};
+
+#[wasm_bindgen(js_name = Instance)]
+pub struct WasmInstance(crate::Instance);
+
+#[wasm_bindgen(js_class = Instance)]
+impl WasmInstance {
+    #[wasm_bindgen(constructor)]
+    pub fn new(sample_rate: f32) -> Result<WasmInstance, JsError> {
+        Ok(WasmInstance(crate::Instance::new(sample_rate)?))
+    }
+
+    pub fn new_with_seed(sample_rate: f32, seed: u64) -> Result<WasmInstance, JsError> {
+        Ok(WasmInstance(crate::Instance::new_with_seed(sample_rate, seed)?))
+    }
+
+    pub fn with_tuning(tuning: &str, sample_rate: f32, seed: Option<u64>) -> Result<WasmInstance, JsError> {
+        let tuning = Tuning::from_name(tuning)
+            .ok_or_else(|| JsError::new(&format!("unknown tuning: {tuning}")))?;
+        Ok(WasmInstance(crate::Instance::with_tuning(tuning, sample_rate, seed)?))
+    }
+
+    pub fn with_partials(partials: &[f32], sample_rate: f32, seed: Option<u64>) -> Result<WasmInstance, JsError> {
+        Ok(WasmInstance(crate::Instance::with_partials(partials, sample_rate, seed)?))
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.0.seed()
+    }
+
+    pub fn set_frequency(&mut self, freq: f32) {
+        self.0.set_frequency(freq);
+    }
+
+    pub fn set_variation_rate(&mut self, rate: f32) {
+        self.0.set_variation_rate(rate);
+    }
+
+    pub fn set_attenuation(&mut self, atten: f32) {
+        self.0.set_attenuation(atten);
+    }
+
+    pub fn set_gain(&mut self, gain: f32) {
+        self.0.set_gain(gain);
+    }
+
+    pub fn set_evolution(&mut self, mode: &str) -> Result<(), JsError> {
+        let mode = Evolution::from_name(mode)
+            .ok_or_else(|| JsError::new(&format!("unknown evolution mode: {mode}")))?;
+        self.0.set_evolution(mode);
+        Ok(())
+    }
+
+    pub fn set_interpolation(&mut self, mode: &str) -> Result<(), JsError> {
+        let mode = Interpolation::from_name(mode)
+            .ok_or_else(|| JsError::new(&format!("unknown interpolation mode: {mode}")))?;
+        self.0.set_interpolation(mode);
+        Ok(())
+    }
+
+    pub fn set_ensemble(&mut self, hamiltonian: &str, unitary: &str) -> Result<(), JsError> {
+        let hamiltonian = Hamiltonian::from_name(hamiltonian)
+            .ok_or_else(|| JsError::new(&format!("unknown Hamiltonian ensemble: {hamiltonian}")))?;
+        let unitary = Unitary::from_name(unitary)
+            .ok_or_else(|| JsError::new(&format!("unknown unitary ensemble: {unitary}")))?;
+        self.0.set_ensemble(hamiltonian, unitary);
+        Ok(())
+    }
+
+    pub fn set_observer_column(&mut self, output: usize, column: usize) {
+        self.0.set_observer(output, &Observer::Column(column));
+    }
+
+    pub fn set_observer_row(&mut self, output: usize, row: usize) {
+        self.0.set_observer(output, &Observer::Row(row));
+    }
+
+    pub fn set_observer_vector(&mut self, output: usize, re: &[f32], im: &[f32]) {
+        let vector = re.iter().zip(im).map(|(&re, &im)| Complex::new(re, im)).collect();
+        self.0.set_observer(output, &Observer::Vector(vector));
+    }
+
+    // Listens to the given column, set_observer_* can change it afterwards.
+    pub fn add_voice(&mut self, channel: usize, column: usize) -> usize {
+        self.0.add_voice(channel, &Observer::Column(column))
+    }
+
+    pub fn clear_voices(&mut self) {
+        self.0.clear_voices();
+    }
+
+    pub fn voice_count(&self) -> usize {
+        self.0.voice_count()
+    }
+
+    // The value is the shift in Hz for the "shift" mode.
+    pub fn set_output(&mut self, output: usize, mode: &str, value: f32) -> Result<(), JsError> {
+        let mode = Output::from_name(mode, value)
+            .ok_or_else(|| JsError::new(&format!("unknown output mode: {mode}")))?;
+        self.0.set_output(output, mode);
+        Ok(())
+    }
+
+    pub fn set_quadrature(&mut self, on: bool) {
+        self.0.set_quadrature(on);
+    }
+
+    // The value is the coupling strength for the "coupled" mode.
+    pub fn set_stereo(&mut self, mode: &str, value: f32) -> Result<(), JsError> {
+        let stereo = Stereo::from_name(mode, value)
+            .ok_or_else(|| JsError::new(&format!("unknown stereo mode: {mode}")))?;
+        self.0.set_stereo(stereo);
+        Ok(())
+    }
+
+    pub fn set_width(&mut self, width: f32) {
+        self.0.set_width(width);
+    }
+
+    pub fn set_observer_motion(&mut self, rate: f32) {
+        self.0.set_observer_motion(rate);
+    }
+
+    pub fn randomize(&mut self) {
+        self.0.randomize();
+    }
+
+    // The value is the interval in seconds, or the rate per second for the
+    // Poisson and drift modes.
+    pub fn set_mutation(&mut self, mode: &str, value: f32, strength: f32) -> Result<(), JsError> {
+        let mutation = Mutation::from_name(mode, value)
+            .ok_or_else(|| JsError::new(&format!("unknown mutation mode: {mode}")))?;
+        self.0.set_mutation(mutation, strength);
+        Ok(())
+    }
+
+    pub fn set_level_target(&mut self, level: usize, probability: f32, magnitude: f32) {
+        self.0.set_level_target(level, Target { probability, magnitude });
+    }
+
+    pub fn set_unit_target(&mut self, probability: f32, magnitude: f32) {
+        self.0.set_unit_target(Target { probability, magnitude });
+    }
+
+    pub fn chain_depth(&self) -> usize {
+        self.0.chain_depth()
+    }
+
+    pub fn set_chain_depth(&mut self, depth: usize) {
+        self.0.set_chain_depth(depth);
+    }
+
+    pub fn set_level_rate(&mut self, level: usize, rate: f32) {
+        self.0.set_level_rate(level, rate);
+    }
+
+    pub fn set_transport(&mut self, transport: &str) -> Result<(), JsError> {
+        let transport = Transport::from_name(transport)
+            .ok_or_else(|| JsError::new(&format!("unknown transport: {transport}")))?;
+        self.0.set_transport(transport);
+        Ok(())
+    }
+
+    pub fn checkpoint(&mut self) {
+        self.0.checkpoint();
+    }
+
+    pub fn position(&self) -> Option<f32> {
+        self.0.position()
+    }
+
+    pub fn scrub(&mut self, position: f32) -> bool {
+        self.0.scrub(position)
+    }
+
+    pub fn set_smoothing_time(&mut self, seconds: f32) {
+        self.0.set_smoothing_time(seconds);
+    }
+
+    pub fn process(&mut self, left: &mut [f32], right: &mut [f32]) {
+        self.0.process(left, right);
+    }
+
+    pub fn process_voices(&mut self, left: &mut [f32], right: &mut [f32], voices: &mut [f32]) {
+        self.0.process_voices(left, right, voices);
+    }
+
+    pub fn process_with_params(&mut self, left: &mut [f32], right: &mut [f32], voices: &mut [f32],
+            frequency: &[f32], gain: &[f32], variation_rate: &[f32]) {
+        self.0.process_with_params(left, right, voices, frequency, gain, variation_rate);
+    }
+
+    pub fn render(&mut self, seconds: f32, interleaved: bool) -> Vec<f32> {
+        self.0.render(seconds, interleaved)
+    }
+
+    pub fn get_sample(&mut self, left: &mut [f32], right: &mut [f32]) {
+        self.0.get_sample(left, right);
+    }
+
+    pub fn snapshot(&self) -> Vec<u8> {
+        self.0.snapshot()
+    }
+
+    pub fn restore(&mut self, data: &[u8]) -> Result<(), JsError> {
+        Ok(self.0.restore(data)?)
+    }
+
+    pub fn morph(&mut self, data: &[u8], seconds: f32) -> Result<(), JsError> {
+        Ok(self.0.morph(data, seconds)?)
+    }
+
+    pub fn is_morphing(&self) -> bool {
+        self.0.is_morphing()
+    }
+}
+
+#[wasm_bindgen]
+pub fn tuning_names() -> Vec<String> {
+    Tuning::ALL.iter().map(|t| t.name().to_owned()).collect()
+}
+
+#[wasm_bindgen]
+pub fn evolution_names() -> Vec<String> {
+    Evolution::ALL.iter().map(|e| e.name().to_owned()).collect()
+}
+
+#[wasm_bindgen]
+pub fn interpolation_names() -> Vec<String> {
+    Interpolation::ALL.iter().map(|i| i.name().to_owned()).collect()
+}
+
+#[wasm_bindgen]
+pub fn hamiltonian_names() -> Vec<String> {
+    Hamiltonian::ALL.iter().map(|h| h.name().to_owned()).collect()
+}
+
+#[wasm_bindgen]
+pub fn unitary_names() -> Vec<String> {
+    Unitary::ALL.iter().map(|u| u.name().to_owned()).collect()
+}
+
+#[wasm_bindgen]
+pub fn transport_names() -> Vec<String> {
+    Transport::ALL.iter().map(|t| t.name().to_owned()).collect()
+}
+
+#[wasm_bindgen]
+pub fn output_names() -> Vec<String> {
+    Output::NAMES.iter().map(|&o| o.to_owned()).collect()
+}
+
+#[wasm_bindgen]
+pub fn stereo_names() -> Vec<String> {
+    Stereo::NAMES.iter().map(|&s| s.to_owned()).collect()
+}
@@ -0,0 +1,206 @@
+use nalgebra::*;
+use rand::{Rng, distr::Uniform};
+
+use crate::params::{Mat, fix_herm, fix_unit};
+
+// Distribution of the random Hamiltonians in the chain. All are normalised
+// by fix_herm afterwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Hamiltonian {
+    // Uniform [-1, 1] complex entries, the original behaviour.
+    #[default]
+    Uniform,
+    // Gaussian unitary ensemble.
+    Gue,
+    // Gaussian orthogonal ensemble (real symmetric).
+    Goe,
+}
+
+// Distribution of the initial unitary.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Unitary {
+    // Uniform [-1, 1] complex entries projected by fix_unit, the original
+    // behaviour. Not Haar distributed.
+    #[default]
+    Uniform,
+    // Haar measure (circular unitary ensemble), via QR of a Ginibre matrix.
+    Haar,
+    // Circular orthogonal ensemble, U^T U for Haar U.
+    Coe,
+}
+
+impl Hamiltonian {
+    pub const ALL: [Hamiltonian; 3] = [
+        Hamiltonian::Uniform,
+        Hamiltonian::Gue,
+        Hamiltonian::Goe,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Hamiltonian::Uniform => "uniform",
+            Hamiltonian::Gue => "gue",
+            Hamiltonian::Goe => "goe",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Hamiltonian> {
+        Hamiltonian::ALL.into_iter().find(|h| h.name() == name)
+    }
+
+    pub(crate) fn sample(self, dim: usize, rng: &mut impl Rng) -> Mat {
+        let m = match self {
+            Hamiltonian::Uniform => {
+                let dist = Uniform::new(-1., 1.).unwrap();
+                Mat::from_fn(dim, dim, |_, _| Complex::new(rng.sample(dist), rng.sample(dist)))
+            },
+            Hamiltonian::Gue => ginibre(dim, rng),
+            Hamiltonian::Goe => Mat::from_fn(dim, dim, |_, _| gaussian(rng).into()),
+        };
+        fix_herm(m)
+    }
+}
+
+impl Unitary {
+    pub const ALL: [Unitary; 3] = [
+        Unitary::Uniform,
+        Unitary::Haar,
+        Unitary::Coe,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Unitary::Uniform => "uniform",
+            Unitary::Haar => "haar",
+            Unitary::Coe => "coe",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Unitary> {
+        Unitary::ALL.into_iter().find(|u| u.name() == name)
+    }
+
+    pub(crate) fn sample(self, dim: usize, rng: &mut impl Rng) -> Mat {
+        match self {
+            Unitary::Uniform => {
+                let dist = Uniform::new(-1., 1.).unwrap();
+                fix_unit(Mat::from_fn(dim, dim, |_, _| Complex::new(rng.sample(dist), rng.sample(dist))))
+            },
+            Unitary::Haar => haar(dim, rng),
+            Unitary::Coe => {
+                let u = haar(dim, rng);
+                u.transpose() * u
+            },
+        }
+    }
+}
+
+// The phases of R's diagonal are moved into Q, otherwise the QR convention
+// biases the distribution (Mezzadri 2007).
+fn haar(dim: usize, rng: &mut impl Rng) -> Mat {
+    let qr = ginibre(dim, rng).qr();
+    let phases = qr.r().diagonal().map(|z| if z.norm() > 0.0 { z / z.norm() } else { 1.0.into() });
+    qr.q() * Mat::from_diagonal(&phases)
+}
+
+fn ginibre(dim: usize, rng: &mut impl Rng) -> Mat {
+    Mat::from_fn(dim, dim, |_, _| Complex::new(gaussian(rng), gaussian(rng)) / 2.0f32.sqrt())
+}
+
+// Box-Muller transform.
+fn gaussian(rng: &mut impl Rng) -> f32 {
+    let u1: f32 = 1.0 - rng.random::<f32>();
+    let u2: f32 = rng.random();
+    (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    const DIM: usize = 5;
+    const SAMPLES: usize = 4000;
+
+    fn samples(unitary: Unitary) -> Vec<Mat> {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        (0..SAMPLES).map(|_| unitary.sample(DIM, &mut rng)).collect()
+    }
+
+    fn mean(values: impl Iterator<Item = f32>) -> f32 {
+        values.sum::<f32>() / SAMPLES as f32
+    }
+
+    #[test]
+    fn haar_moments() {
+        let us = samples(Unitary::Haar);
+        let n = DIM as f32;
+        for i in 0..DIM {
+            for j in 0..DIM {
+                let m2 = mean(us.iter().map(|u| u[(i, j)].norm_sqr()));
+                assert!((m2 - 1.0 / n).abs() < 0.02, "E|U_{i}{j}|^2 = {m2}");
+            }
+        }
+        let m4 = mean(us.iter().map(|u| u[(0, 0)].norm_sqr().powi(2)));
+        assert!((m4 - 2.0 / (n * (n + 1.0))).abs() < 0.01, "E|U_11|^4 = {m4}");
+    }
+
+    #[test]
+    fn coe_moments() {
+        let us = samples(Unitary::Coe);
+        let m2 = mean(us.iter().map(|u| u[(0, 0)].norm_sqr()));
+        assert!((m2 - 2.0 / (DIM as f32 + 1.0)).abs() < 0.02, "E|S_11|^2 = {m2}");
+    }
+
+    #[test]
+    fn unitary_samples() {
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        for unitary in Unitary::ALL {
+            for _ in 0..100 {
+                let u = unitary.sample(DIM, &mut rng);
+                let err = (u.ad_mul(&u) - Mat::identity(DIM, DIM)).norm();
+                assert!(err < 1e-4, "{unitary:?}: |U^+U - 1| = {err}");
+            }
+        }
+    }
+
+    #[test]
+    fn hamiltonian_samples() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        for hamiltonian in [Hamiltonian::Gue, Hamiltonian::Goe] {
+            for _ in 0..100 {
+                let h = hamiltonian.sample(DIM, &mut rng);
+                assert!((&h - h.adjoint()).norm() < 1e-6, "{hamiltonian:?} not Hermitian");
+                assert!(h.trace().norm() < 1e-5, "{hamiltonian:?} trace {}", h.trace());
+                assert!((h.norm() - 1.0).abs() < 1e-5, "{hamiltonian:?} norm {}", h.norm());
+                if hamiltonian == Hamiltonian::Goe {
+                    assert!(h.iter().all(|z| z.im == 0.0), "GOE sample not real");
+                    assert_eq!(h, h.transpose(), "GOE sample not symmetric");
+                }
+            }
+        }
+    }
+
+    // E h_ii^2 / E |h_ij|^2 is 1 for GUE and 2 for GOE. Removing the trace
+    // takes away 1/n of the diagonal variance, normalising does not change
+    // the ratio.
+    #[test]
+    fn hamiltonian_moments() {
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        let n = DIM as f32;
+        for (hamiltonian, ratio) in [(Hamiltonian::Gue, 1.0), (Hamiltonian::Goe, 2.0)] {
+            let hs: Vec<Mat> = (0..SAMPLES).map(|_| hamiltonian.sample(DIM, &mut rng)).collect();
+            let diagonal = mean(hs.iter().map(|h| h.diagonal().iter().map(|z| z.norm_sqr()).sum::<f32>() / n));
+            let off = mean(hs.iter().map(|h| {
+                let total = h.iter().map(|z| z.norm_sqr()).sum::<f32>();
+                let diag = h.diagonal().iter().map(|z| z.norm_sqr()).sum::<f32>();
+                (total - diag) / (n * (n - 1.0))
+            }));
+            let expected = ratio * (1.0 - 1.0 / n);
+            assert!((diagonal / off - expected).abs() < 0.05 * expected,
+                "{hamiltonian:?} ratio {} instead of {expected}", diagonal / off);
+        }
+    }
+}
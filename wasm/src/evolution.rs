@@ -0,0 +1,248 @@
+use nalgebra::*;
+
+use crate::params::{Mat, Params};
+
+// Largest phase advance (dt times the norm of the generators) of a single
+// integration step, longer intervals are subdivided.
+const MAX_STEP: f32 = 0.01;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Evolution {
+    // First-order step, drifts off the unitary manifold until normalize().
+    #[default]
+    Euler,
+    // Classical fourth-order Runge-Kutta, accurate but not structure-preserving.
+    Rk4,
+    // Second-order Magnus expansion (exponential midpoint rule).
+    Magnus,
+    // Exact propagators exp(iH dt) of the generators frozen at the start of
+    // each substep. Unitary, but only first order like Euler.
+    Exponential,
+    // Same splitting with the Cayley transform, cheaper than eigenvectors.
+    Cayley,
+}
+
+impl Evolution {
+    pub const ALL: [Evolution; 5] = [
+        Evolution::Euler,
+        Evolution::Rk4,
+        Evolution::Magnus,
+        Evolution::Exponential,
+        Evolution::Cayley,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Evolution::Euler => "euler",
+            Evolution::Rk4 => "rk4",
+            Evolution::Magnus => "magnus",
+            Evolution::Exponential => "exponential",
+            Evolution::Cayley => "cayley",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Evolution> {
+        Evolution::ALL.into_iter().find(|e| e.name() == name)
+    }
+}
+
+impl Params {
+    // The substeps are bounded by MAX_STEP, so Rk4 and Magnus converge to
+    // the same curve regardless of the block size. The first-order modes
+    // still depend slightly on how the time is split. The cost is
+    // proportional to dt and the rates, which is why those are clamped where
+    // they are set.
+    pub(crate) fn evolve(&mut self, dt: f32, mode: Evolution) {
+        if dt == 0.0 || !dt.is_finite() {
+            return;
+        }
+        let norm = self.herm.iter().zip(&self.rates)
+            .map(|(mx, rate)| mx.norm() * rate.abs())
+            .fold(0.0, f32::max);
+        let steps = ((dt.abs() * norm / MAX_STEP).ceil() as usize).max(1);
+        let dt = dt / (steps as f32);
+        for _ in 0..steps {
+            match mode {
+                Evolution::Euler => self.euler_step(dt),
+                Evolution::Rk4 => self.rk4_step(dt),
+                Evolution::Magnus => self.magnus_step(dt),
+                Evolution::Exponential => self.propagate(dt, expi),
+                Evolution::Cayley => self.propagate(dt, cayley),
+            }
+        }
+    }
+
+    fn euler_step(&mut self, dt: f32) {
+        let depth = self.herm.len();
+        for ix in 1..depth {
+            let i_dt = Complex::new(0.0, dt * self.rates[ix - 1]);
+            let comm = &self.herm[ix - 1] * &self.herm[ix] - &self.herm[ix] * &self.herm[ix - 1];
+            self.herm[ix] += comm * i_dt;
+        }
+        let i_dt = Complex::new(0.0, dt * self.rates[depth - 1]);
+        let step = &self.herm[depth - 1] * &self.unit * i_dt;
+        self.unit += step;
+    }
+
+    fn rk4_step(&mut self, dt: f32) {
+        let state: Vec<Mat> = self.herm.iter().chain([&self.unit]).cloned().collect();
+        let k1 = derivative(&state, &self.rates);
+        let k2 = derivative(&shifted(&state, &k1, dt / 2.0), &self.rates);
+        let k3 = derivative(&shifted(&state, &k2, dt / 2.0), &self.rates);
+        let k4 = derivative(&shifted(&state, &k3, dt), &self.rates);
+        let dt6 = Complex::from(dt / 6.0);
+        for (ix, target) in self.herm.iter_mut().chain([&mut self.unit]).enumerate() {
+            *target += (&k1[ix] + (&k2[ix] + &k3[ix]) * Complex::from(2.0) + &k4[ix]) * dt6;
+        }
+    }
+
+    // Each level is propagated by the generator below it taken at the
+    // midpoint of the step.
+    fn magnus_step(&mut self, dt: f32) {
+        let depth = self.herm.len();
+        let mut mid = self.clone();
+        mid.propagate(dt / 2.0, expi);
+        for ix in 1..depth {
+            let u = expi(&mid.herm[ix - 1], dt * self.rates[ix - 1]);
+            self.herm[ix] = &u * &self.herm[ix] * u.adjoint();
+        }
+        self.unit = expi(&mid.herm[depth - 1], dt * self.rates[depth - 1]) * &self.unit;
+    }
+
+    // dH/dt = i[G, H] is solved by H -> U H U^+ with U = exp(iG dt) if G is
+    // constant, so each level of the chain is conjugated by the propagator of
+    // the level below. All propagators are taken before any level moves.
+    fn propagate(&mut self, dt: f32, prop: fn(&Mat, f32) -> Mat) {
+        let props: Vec<Mat> = self.herm.iter().zip(&self.rates)
+            .map(|(herm, rate)| prop(herm, dt * rate))
+            .collect();
+        for (herm, u) in self.herm.iter_mut().skip(1).zip(&props) {
+            *herm = u * &*herm * u.adjoint();
+        }
+        self.unit = props.last().unwrap() * &self.unit;
+    }
+}
+
+// Right-hand side of the chain equations for the state [herm..., unit]:
+// herm[0] is constant, dH_k/dt = i r_k-1 [H_k-1, H_k] and dU/dt = i r H_last U.
+fn derivative(state: &[Mat], rates: &[f32]) -> Vec<Mat> {
+    let dim = state[0].nrows();
+    let unit = state.len() - 1;
+    let mut ret = vec![Mat::zeros(dim, dim)];
+    for ix in 1..state.len() {
+        let (g, h) = (&state[ix - 1], &state[ix]);
+        let d = if ix < unit { g * h - h * g } else { g * h };
+        ret.push(d * Complex::new(0.0, rates[ix - 1]));
+    }
+    ret
+}
+
+fn shifted(state: &[Mat], delta: &[Mat], dt: f32) -> Vec<Mat> {
+    state.iter().zip(delta).map(|(s, d)| s + d * Complex::from(dt)).collect()
+}
+
+// The propagators are computed in double precision: single-precision
+// eigenvectors are orthonormal only to about 1e-6, which adds up over time.
+pub(crate) type Mat64 = DMatrix<Complex<f64>>;
+
+// exp(iH dt) for a Hermitian H.
+pub(crate) fn expi(h: &Mat, dt: f32) -> Mat {
+    let eigen = widen(h).symmetric_eigen();
+    let phases = eigen.eigenvalues.map(|l| Complex::new(0.0, l * dt as f64).exp());
+    narrow(&eigen.eigenvectors * Mat64::from_diagonal(&phases) * eigen.eigenvectors.adjoint())
+}
+
+// (1 - iH dt/2)^-1 (1 + iH dt/2), a unitary approximation of exp(iH dt).
+fn cayley(h: &Mat, dt: f32) -> Mat {
+    let dim = h.nrows();
+    let a = widen(h) * Complex::new(0.0, dt as f64 / 2.0);
+    let id = Mat64::identity(dim, dim);
+    narrow((&id - &a).try_inverse().unwrap() * (&id + &a))
+}
+
+pub(crate) fn widen(m: &Mat) -> Mat64 {
+    m.map(|z| Complex::new(z.re as f64, z.im as f64))
+}
+
+pub(crate) fn narrow(m: Mat64) -> Mat {
+    m.map(|z| Complex::new(z.re as f32, z.im as f32))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::{Hamiltonian, Unitary};
+
+    fn params() -> Params {
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        Params::new(6, 3, (Hamiltonian::Gue, Unitary::Haar), &mut rng)
+    }
+
+    // Evolves for the given time in blocks of the given size.
+    fn evolved(mode: Evolution, total: f32, block: f32) -> Mat {
+        let mut p = params();
+        let blocks = (total / block).round() as usize;
+        for _ in 0..blocks {
+            p.evolve(block, mode);
+        }
+        p.unit
+    }
+
+    #[test]
+    fn higher_order_modes_ignore_block_size() {
+        for mode in [Evolution::Rk4, Evolution::Magnus] {
+            let reference = evolved(mode, 0.5, 0.002);
+            for block in [0.5, 0.1, 0.0125, 0.005] {
+                let err = (evolved(mode, 0.5, block) - &reference).norm();
+                assert!(err < 1e-3, "{mode:?} block {block}: {err}");
+            }
+        }
+    }
+
+    // Below MAX_STEP the block is a single substep. The first-order modes
+    // halve their error with it, Rk4 and Magnus are at the rounding floor of
+    // f32 already.
+    #[test]
+    fn modes_converge_as_blocks_shrink() {
+        let reference = evolved(Evolution::Rk4, 0.2, 0.0002);
+        let error = |mode, block| (evolved(mode, 0.2, block) - &reference).norm();
+        for mode in [Evolution::Euler, Evolution::Exponential, Evolution::Cayley] {
+            let errors = [0.008, 0.004, 0.002].map(|block| error(mode, block));
+            assert!(errors.windows(2).all(|e| e[1] < 0.6 * e[0]), "{mode:?}: {errors:?}");
+        }
+        for mode in [Evolution::Rk4, Evolution::Magnus] {
+            for block in [0.008, 0.004, 0.002] {
+                let err = error(mode, block);
+                assert!(err < 5e-5, "{mode:?} block {block}: {err}");
+            }
+        }
+    }
+
+    #[test]
+    fn unitary_stays_unitary() {
+        for mode in [Evolution::Rk4, Evolution::Magnus, Evolution::Exponential, Evolution::Cayley] {
+            let u = evolved(mode, 5.0, 0.01);
+            let err = (u.ad_mul(&u) - Mat::identity(6, 6)).norm();
+            assert!(err < 1e-3, "{mode:?}: {err}");
+        }
+    }
+
+    #[test]
+    fn reverse_retraces() {
+        let mut p = params();
+        p.evolve(1.0, Evolution::Rk4);
+        p.evolve(-1.0, Evolution::Rk4);
+        assert!((p.unit - params().unit).norm() < 1e-3);
+    }
+
+    #[test]
+    fn non_finite_time_is_ignored() {
+        let mut p = params();
+        p.evolve(f32::NAN, Evolution::Euler);
+        p.evolve(f32::INFINITY, Evolution::Rk4);
+        assert_eq!(p.unit, params().unit);
+    }
+}
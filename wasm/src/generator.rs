@@ -0,0 +1,342 @@
+use nalgebra::{Complex, ComplexField};
+
+use crate::Evolution;
+use crate::observer::View;
+use crate::params::{Mat, Params};
+
+const ATTEN: f32 = 0.0;
+
+#[derive(Clone)]
+pub(crate) struct Generator {
+    partials: Vec<f32>,
+    pub(crate) freq_step: Smoothed,
+    pub(crate) atten: Smoothed,
+    pub(crate) gain: Smoothed,
+    pub(crate) par_step: Smoothed,
+    pub(crate) evolution: Evolution,
+    pub(crate) interpolation: Interpolation,
+    pub(crate) direction: f32,
+    pub(crate) view: View,
+    pub(crate) output: Output,
+    // Per-sample rotation for Output::Shift and its running phase.
+    pub(crate) shift_step: Complex<f32>,
+    shift: Complex<f32>,
+    cx_step: Vec<Complex<f32>>,
+    weight: Vec<f32>,
+    pub(crate) cx: Vec<Complex<f32>>,
+}
+
+// How the partial coefficients move from their value at the start of a
+// block to the value after evolve().
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    // Jump at the block start.
+    Step,
+    #[default]
+    Linear,
+    // Along the great circle, keeping the coefficient vector normalised.
+    Geodesic,
+}
+
+// What is taken from the complex (analytic) sum of the partials.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Output {
+    #[default]
+    Real,
+    // 90° behind the real part.
+    Imaginary,
+    // Single-sideband: the whole spectrum moved by the given number of Hz.
+    Shift(f32),
+    // The magnitude, for use as a modulation source.
+    Envelope,
+}
+
+// Per-sample (a-rate) values overriding the smoothed controls for one block.
+// Empty slices leave the respective control alone.
+#[derive(Default)]
+pub(crate) struct Automation<'a> {
+    pub(crate) freq_step: &'a [f32],
+    pub(crate) gain: &'a [f32],
+}
+
+// Linear ramp towards a target value over a given number of samples.
+#[derive(Clone, Copy)]
+pub(crate) struct Smoothed {
+    value: f32,
+    target: f32,
+    step: f32,
+    remaining: u32,
+}
+
+impl Generator {
+    pub(crate) fn new(partials: &[f32], dt1: f32, dt2: f32) -> Generator {
+        let cx = vec![1.0.into(); partials.len()];
+        let mut generator = Generator {
+            partials: partials.to_vec(),
+            freq_step: Smoothed::new(dt1),
+            atten: Smoothed::new(ATTEN),
+            gain: Smoothed::new(1.0),
+            par_step: Smoothed::new(dt2),
+            evolution: Evolution::default(),
+            interpolation: Interpolation::default(),
+            direction: 1.0,
+            view: View::new(partials.len()),
+            output: Output::default(),
+            shift_step: 1.0.into(),
+            shift: 1.0.into(),
+            cx_step: Vec::new(),
+            weight: Vec::new(),
+            cx,
+        };
+        generator.set_freq_step(dt1);
+        generator.set_attenuation(ATTEN);
+        generator
+    }
+
+    // Only the steps change, the running phases in cx are kept intact.
+    fn set_freq_step(&mut self, dt1: f32) {
+        self.cx_step = self.partials.iter().map(|m| Complex::new(0.0, m * dt1).exp()).collect();
+    }
+
+    // Spectral tilt: partial m is weighted by m^-atten.
+    fn set_attenuation(&mut self, atten: f32) {
+        let divider = (self.partials.len() as f32).sqrt();
+        self.weight = self.partials.iter().map(|m| m.powf(-atten) / divider).collect();
+    }
+
+    // Without a ramp the new value has to take effect here, synthesize()
+    // only follows ongoing ramps.
+    pub(crate) fn ramp_freq_step(&mut self, dt1: f32, samples: u32) {
+        self.freq_step.set(dt1, samples);
+        if !self.freq_step.is_ramping() {
+            let dt1 = self.freq_step.next();
+            self.set_freq_step(dt1);
+        }
+    }
+
+    pub(crate) fn ramp_attenuation(&mut self, atten: f32, samples: u32) {
+        self.atten.set(atten, samples);
+        if !self.atten.is_ramping() {
+            let atten = self.atten.next();
+            self.set_attenuation(atten);
+        }
+    }
+
+    // Advances the variation rate ramp, returns the evolution time of the
+    // next block.
+    pub(crate) fn time_step(&mut self, samples: usize) -> f32 {
+        (0..samples).map(|_| self.par_step.next()).sum::<f32>() * self.direction
+    }
+
+    pub(crate) fn generate(&mut self, data: &mut [f32], params: &mut Params, automation: &Automation) {
+        let dt = self.time_step(data.len());
+        let before = params.unit.clone();
+        params.evolve(dt, self.evolution);
+        self.follow(data, &before, &params.unit, dt, automation);
+    }
+
+    // Plays the coefficients of a unitary evolved elsewhere, moving from
+    // before to after over the block.
+    pub(crate) fn follow(&mut self, data: &mut [f32], before: &Mat, after: &Mat, dt: f32, automation: &Automation) {
+        let from = self.view.observe_start(before);
+        self.view.advance(dt);
+        let to = self.view.observe(after);
+        self.synthesize(data, from, to, automation);
+    }
+
+    fn synthesize(&mut self, data: &mut [f32], from: Vec<Complex<f32>>, to: Vec<Complex<f32>>,
+            automation: &Automation) {
+        let mut coef = to.clone();
+        let theta = match self.interpolation {
+            Interpolation::Geodesic => angle(&from, &to),
+            _ => 0.0,
+        };
+        let len = data.len() as f32;
+        for (i, x) in data.iter_mut().enumerate() {
+            let t = (i + 1) as f32 / len;
+            match self.interpolation {
+                Interpolation::Step => (),
+                Interpolation::Linear => lerp(&from, &to, t, &mut coef),
+                Interpolation::Geodesic => slerp(&from, &to, theta, t, &mut coef),
+            }
+            if let Some(&dt1) = automation.freq_step.get(i) {
+                self.freq_step.jump(dt1);
+                self.set_freq_step(dt1);
+            } else if self.freq_step.is_ramping() {
+                let dt1 = self.freq_step.next();
+                self.set_freq_step(dt1);
+            }
+            if self.atten.is_ramping() {
+                let atten = self.atten.next();
+                self.set_attenuation(atten);
+            }
+            let mut res: Complex<f32> = 0.0.into();
+            for (ix, cx) in self.cx.iter_mut().enumerate() {
+                *cx *= self.cx_step[ix];
+                res += *cx * coef[ix] * self.weight[ix];
+            }
+            if let Some(&gain) = automation.gain.get(i) {
+                self.gain.jump(gain);
+            }
+            let y = match self.output {
+                Output::Real => res.re,
+                Output::Imaginary => res.im,
+                Output::Shift(_) => {
+                    self.shift *= self.shift_step;
+                    (res * self.shift).re
+                },
+                Output::Envelope => res.abs(),
+            };
+            *x = y * self.gain.next();
+        }
+    }
+
+    pub(crate) fn normalize(&mut self) {
+        for z in &mut self.cx {
+            *z /= z.abs();
+        }
+        self.shift /= self.shift.abs();
+    }
+}
+
+impl Interpolation {
+    pub const ALL: [Interpolation; 3] = [
+        Interpolation::Step,
+        Interpolation::Linear,
+        Interpolation::Geodesic,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Interpolation::Step => "step",
+            Interpolation::Linear => "linear",
+            Interpolation::Geodesic => "geodesic",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Interpolation> {
+        Interpolation::ALL.into_iter().find(|i| i.name() == name)
+    }
+}
+
+impl Output {
+    pub const NAMES: [&str; 4] = ["real", "imaginary", "shift", "envelope"];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Output::Real => "real",
+            Output::Imaginary => "imaginary",
+            Output::Shift(_) => "shift",
+            Output::Envelope => "envelope",
+        }
+    }
+
+    // The value is the shift in Hz, ignored by the other modes.
+    pub fn from_name(name: &str, value: f32) -> Option<Output> {
+        match name {
+            "real" => Some(Output::Real),
+            "imaginary" => Some(Output::Imaginary),
+            "shift" => Some(Output::Shift(value)),
+            "envelope" => Some(Output::Envelope),
+            _ => None,
+        }
+    }
+}
+
+// Angle between two unit vectors of C^n seen as R^2n.
+fn angle(a: &[Complex<f32>], b: &[Complex<f32>]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| (x.conj() * y).re).sum();
+    dot.clamp(-1.0, 1.0).acos()
+}
+
+fn lerp(a: &[Complex<f32>], b: &[Complex<f32>], t: f32, out: &mut [Complex<f32>]) {
+    for ((o, x), y) in out.iter_mut().zip(a).zip(b) {
+        *o = x + (y - x) * t;
+    }
+}
+
+fn slerp(a: &[Complex<f32>], b: &[Complex<f32>], theta: f32, t: f32, out: &mut [Complex<f32>]) {
+    if theta < 1e-4 {
+        return lerp(a, b, t, out);
+    }
+    let wa = ((1.0 - t) * theta).sin() / theta.sin();
+    let wb = (t * theta).sin() / theta.sin();
+    for ((o, x), y) in out.iter_mut().zip(a).zip(b) {
+        *o = x * wa + y * wb;
+    }
+}
+
+impl<'a> Automation<'a> {
+    pub(crate) fn slice(&self, range: std::ops::Range<usize>) -> Automation<'a> {
+        let part = |values: &'a [f32]| if values.is_empty() { values } else { &values[range.clone()] };
+        Automation {
+            freq_step: part(self.freq_step),
+            gain: part(self.gain),
+        }
+    }
+}
+
+impl Smoothed {
+    pub(crate) fn new(value: f32) -> Smoothed {
+        Smoothed { value, target: value, step: 0.0, remaining: 0 }
+    }
+
+    pub(crate) fn set(&mut self, target: f32, samples: u32) {
+        if target == self.target {
+            return;
+        }
+        self.target = target;
+        if samples == 0 {
+            self.value = target;
+            self.remaining = 0;
+        } else {
+            self.step = (target - self.value) / (samples as f32);
+            self.remaining = samples;
+        }
+    }
+
+    pub(crate) fn jump(&mut self, value: f32) {
+        self.value = value;
+        self.target = value;
+        self.remaining = 0;
+    }
+
+    pub(crate) fn next(&mut self) -> f32 {
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            self.value = if self.remaining == 0 { self.target } else { self.value + self.step };
+        }
+        self.value
+    }
+
+    pub(crate) fn is_ramping(&self) -> bool {
+        self.remaining > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Instance, Interpolation, Mutation};
+
+    // Largest jump between consecutive samples of the left channel, with the
+    // oscillators standing still so that only the coefficients move.
+    fn max_step(interpolation: Interpolation) -> f32 {
+        let mut inst = Instance::new_with_seed(48000.0, 6).unwrap();
+        inst.set_smoothing_time(0.0);
+        inst.set_frequency(0.0);
+        inst.set_variation_rate(100.0);
+        inst.set_mutation(Mutation::Off, 0.0);
+        inst.set_interpolation(interpolation);
+        let left: Vec<f32> = inst.render(0.1, true).into_iter().step_by(2).collect();
+        left.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn interpolation_is_continuous() {
+        let step = max_step(Interpolation::Step);
+        for interpolation in [Interpolation::Linear, Interpolation::Geodesic] {
+            let smooth = max_step(interpolation);
+            assert!(smooth < step / 20.0, "{interpolation:?}: {smooth} vs {step}");
+        }
+    }
+}
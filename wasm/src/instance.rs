@@ -0,0 +1,606 @@
+use nalgebra::Complex;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Evolution, Hamiltonian, Interpolation, Mutation, Observer, Output, Stereo, Target, Transport, Tuning, Unitary};
+use crate::params::{Params, ITER, MAX_ITER, MAX_LEVEL_RATE};
+use crate::generator::{Automation, Generator, Smoothed};
+use crate::transport::Checkpoint;
+use crate::morph::Morph;
+use crate::observer::View;
+
+const FREQ: f32 = 100.0;
+const VAR_RATE: f32 = 1.0;
+const SAMPLES: usize = 128;
+const SMOOTHING: f32 = 0.02;
+// The cost of the evolution grows with its speed, these ceilings keep a
+// default chain within the real-time budget.
+pub(crate) const MAX_VARIATION_RATE: f32 = 100.0;
+
+#[derive(Debug)]
+pub enum InstanceError {
+    NoPartials,
+    Partial(f32),
+    SampleRate(f32),
+}
+
+impl std::fmt::Display for InstanceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            InstanceError::NoPartials => write!(f, "at least one partial is needed"),
+            InstanceError::Partial(ratio) => write!(f, "invalid partial ratio {ratio}"),
+            InstanceError::SampleRate(sr) => write!(f, "invalid sample rate {sr}"),
+        }
+    }
+}
+
+impl std::error::Error for InstanceError { }
+
+pub struct Instance {
+    // ChaCha is platform-independent, unlike SmallRng, so a seed draws the
+    // same numbers everywhere. The output is bit-identical only on the same
+    // platform, the float functions differ slightly between targets.
+    pub(crate) rng: ChaCha8Rng,
+    pub(crate) seed: u64,
+    hamiltonian: Hamiltonian,
+    unitary: Unitary,
+    pub(crate) sample_rate: f32,
+    pub(crate) ramp: u32,
+    pub(crate) partials: Vec<f32>,
+    pub(crate) params: [Params; 2],
+    // One per channel, followed by the extra voices.
+    pub(crate) generator: Vec<Generator>,
+    // The channel whose parameters each extra voice listens to.
+    voices: Vec<usize>,
+    pub(crate) stereo: Stereo,
+    // What set_quadrature() replaced: stereo mode, right observer and output.
+    pub(crate) quadrature: Option<(Stereo, View, Output)>,
+    pub(crate) width: Smoothed,
+    pub(crate) fix_elapsed: u32,
+    pub(crate) fix_interval: u32,
+    pub(crate) mutation: Mutation,
+    mutation_strength: f32,
+    // Indexed by chain level.
+    level_targets: [Target; MAX_ITER],
+    unit_target: Target,
+    // Samples until the next discrete mutation event.
+    pub(crate) mutation_countdown: Option<u32>,
+    pub(crate) transport: Transport,
+    pub(crate) checkpoint: Option<Checkpoint>,
+    pub(crate) morph: Option<[Morph; 2]>,
+}
+
+impl Instance {
+    pub fn new(sample_rate: f32) -> Result<Instance, InstanceError> {
+        Instance::with_tuning(Tuning::default(), sample_rate, None)
+    }
+
+    pub fn new_with_seed(sample_rate: f32, seed: u64) -> Result<Instance, InstanceError> {
+        Instance::with_tuning(Tuning::default(), sample_rate, Some(seed))
+    }
+
+    pub fn with_tuning(tuning: Tuning, sample_rate: f32, seed: Option<u64>) -> Result<Instance, InstanceError> {
+        Instance::with_partials(&tuning.partials(), sample_rate, seed)
+    }
+
+    pub fn with_partials(partials: &[f32], sample_rate: f32, seed: Option<u64>) -> Result<Instance, InstanceError> {
+        if partials.is_empty() {
+            return Err(InstanceError::NoPartials);
+        }
+        if let Some(&ratio) = partials.iter().find(|r| !(r.is_finite() && **r > 0.0)) {
+            return Err(InstanceError::Partial(ratio));
+        }
+        // Below 1 Hz the renormalisation interval of one second would be
+        // zero samples long.
+        if !(sample_rate.is_finite() && sample_rate >= 1.0) {
+            return Err(InstanceError::SampleRate(sample_rate));
+        }
+        let seed = seed.unwrap_or_else(|| rand::rng().random());
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let dim = partials.len();
+        let ensemble = (Hamiltonian::default(), Unitary::default());
+        let params = [Params::new(dim, ITER, ensemble, &mut rng), Params::new(dim, ITER, ensemble, &mut rng)];
+        let dt1 = FREQ / sample_rate * std::f32::consts::TAU;
+        let dt2 = VAR_RATE / sample_rate;
+        let generator = vec![Generator::new(partials, dt1, dt2), Generator::new(partials, dt1, dt2)];
+        Ok(Instance {
+            rng,
+            seed,
+            hamiltonian: ensemble.0,
+            unitary: ensemble.1,
+            sample_rate,
+            ramp: (SMOOTHING * sample_rate) as u32,
+            partials: partials.to_vec(),
+            params,
+            generator,
+            voices: Vec::new(),
+            stereo: Stereo::default(),
+            quadrature: None,
+            width: Smoothed::new(1.0),
+            fix_elapsed: 0,
+            fix_interval: sample_rate as u32,
+            mutation: Mutation::default(),
+            mutation_strength: 1.0,
+            level_targets: std::array::from_fn(|level| if level == 0 { Target::FULL } else { Target::OFF }),
+            unit_target: Target::OFF,
+            mutation_countdown: Some(sample_rate as u32),
+            transport: Transport::default(),
+            checkpoint: None,
+            morph: None,
+        })
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn set_frequency(&mut self, freq: f32) {
+        let dt1 = freq / self.sample_rate * std::f32::consts::TAU;
+        for generator in &mut self.generator {
+            generator.ramp_freq_step(dt1, self.ramp);
+        }
+    }
+
+    // Clamped to MAX_VARIATION_RATE.
+    pub fn set_variation_rate(&mut self, rate: f32) {
+        let rate = rate.clamp(0.0, MAX_VARIATION_RATE);
+        for generator in &mut self.generator {
+            generator.par_step.set(rate / self.sample_rate, self.ramp);
+        }
+    }
+
+    pub fn set_attenuation(&mut self, atten: f32) {
+        for generator in &mut self.generator {
+            generator.ramp_attenuation(atten, self.ramp);
+        }
+    }
+
+    pub fn set_gain(&mut self, gain: f32) {
+        for generator in &mut self.generator {
+            generator.gain.set(gain, self.ramp);
+        }
+    }
+
+    pub fn set_evolution(&mut self, mode: Evolution) {
+        for generator in &mut self.generator {
+            generator.evolution = mode;
+        }
+    }
+
+    pub fn set_interpolation(&mut self, mode: Interpolation) {
+        for generator in &mut self.generator {
+            generator.interpolation = mode;
+        }
+    }
+
+    // Outputs are the two channels followed by the extra voices, others are
+    // ignored. The new direction is faded in over the next block.
+    pub fn set_observer(&mut self, output: usize, observer: &Observer) {
+        if let Some(generator) = self.generator.get_mut(output) {
+            generator.view.set(observer, self.partials.len());
+        }
+    }
+
+    // Adds an output listening to the same evolution as the given channel
+    // from another direction, e.g. another column of the unitary. Returns the
+    // index of the new output.
+    pub fn add_voice(&mut self, channel: usize, observer: &Observer) -> usize {
+        let channel = channel.min(1);
+        let mut generator = self.generator[channel].clone();
+        generator.view.set(observer, self.partials.len());
+        self.generator.push(generator);
+        self.voices.push(channel);
+        self.generator.len() - 1
+    }
+
+    pub fn clear_voices(&mut self) {
+        self.generator.truncate(2);
+        self.voices.clear();
+    }
+
+    pub fn voice_count(&self) -> usize {
+        self.voices.len()
+    }
+
+    // Outputs are numbered as for set_observer().
+    pub fn set_output(&mut self, output: usize, mode: Output) {
+        if let Some(generator) = self.generator.get_mut(output) {
+            if let Output::Shift(hz) = mode {
+                generator.shift_step = Complex::new(0.0, hz / self.sample_rate * std::f32::consts::TAU).exp();
+            }
+            generator.output = mode;
+        }
+    }
+
+    // Lets the observer vectors turn under a random Hamiltonian of their own,
+    // at the given rate in radians per unit of evolution time. 0 stops them.
+    pub fn set_observer_motion(&mut self, rate: f32) {
+        for generator in &mut self.generator {
+            if !generator.view.set_motion_rate(rate) {
+                let h = self.hamiltonian.sample(self.partials.len(), &mut self.rng);
+                generator.view.set_motion(&h, rate);
+            }
+        }
+    }
+
+    // Applies to all matrices drawn from now on: mutations, new chain levels
+    // and randomize().
+    pub fn set_ensemble(&mut self, hamiltonian: Hamiltonian, unitary: Unitary) {
+        self.hamiltonian = hamiltonian;
+        self.unitary = unitary;
+    }
+
+    // Draws a fresh state for both channels, keeping the chain depth and rates.
+    pub fn randomize(&mut self) {
+        let dim = self.partials.len();
+        let ensemble = (self.hamiltonian, self.unitary);
+        for params in &mut self.params {
+            let mut fresh = Params::new(dim, params.herm.len(), ensemble, &mut self.rng);
+            fresh.rates = std::mem::take(&mut params.rates);
+            *params = fresh;
+        }
+        self.morph = None;
+        self.desync_checkpoint();
+    }
+
+    // Strength is the portion of the new matrix blended in at each event, or
+    // scales the rate of the continuous drift.
+    pub fn set_mutation(&mut self, mutation: Mutation, strength: f32) {
+        self.mutation = mutation;
+        self.mutation_strength = strength.clamp(0.0, 1.0);
+        self.mutation_countdown = mutation.countdown(self.sample_rate, &mut self.rng);
+    }
+
+    // Levels beyond the current chain depth are kept but have no effect.
+    pub fn set_level_target(&mut self, level: usize, target: Target) {
+        if let Some(t) = self.level_targets.get_mut(level) {
+            *t = target;
+        }
+    }
+
+    pub fn set_unit_target(&mut self, target: Target) {
+        self.unit_target = target;
+    }
+
+    pub fn chain_depth(&self) -> usize {
+        self.params[0].herm.len()
+    }
+
+    // 1 means a static Hamiltonian (up to mutations), every further level
+    // makes the one below it rotate.
+    pub fn set_chain_depth(&mut self, depth: usize) {
+        let depth = depth.clamp(1, MAX_ITER);
+        for params in &mut self.params {
+            params.set_depth(depth, self.hamiltonian, &mut self.rng);
+        }
+        self.morph = None;
+        self.desync_checkpoint();
+    }
+
+    // Levels beyond the current chain depth are ignored, rates are clamped
+    // to MAX_LEVEL_RATE either way.
+    pub fn set_level_rate(&mut self, level: usize, rate: f32) {
+        let rate = rate.clamp(-MAX_LEVEL_RATE, MAX_LEVEL_RATE);
+        for params in &mut self.params {
+            if let Some(r) = params.rates.get_mut(level) {
+                *r = rate;
+            }
+        }
+    }
+
+    pub fn set_smoothing_time(&mut self, seconds: f32) {
+        self.ramp = (seconds.max(0.0) * self.sample_rate) as u32;
+    }
+
+    pub fn process(&mut self, left: &mut [f32], right: &mut [f32]) {
+        self.process_automated(left, right, &mut [], &Automation::default());
+    }
+
+    // The voices are planar in one buffer, voice_count() times the channel
+    // length. An empty buffer skips them.
+    pub fn process_voices(&mut self, left: &mut [f32], right: &mut [f32], voices: &mut [f32]) {
+        self.process_automated(left, right, voices, &Automation::default());
+    }
+
+    // Block-rate values (slices of length 1) go through the usual smoothing,
+    // a-rate values (one per frame) are followed sample by sample. Slices of
+    // any other length are taken as their mean at block rate.
+    pub fn process_with_params(&mut self, left: &mut [f32], right: &mut [f32], voices: &mut [f32],
+            frequency: &[f32], gain: &[f32], variation_rate: &[f32]) {
+        let frames = left.len();
+        let mean = |values: &[f32]| values.iter().sum::<f32>() / (values.len() as f32);
+        let freq_step: Vec<f32>;
+        let mut automation = Automation::default();
+        match frequency {
+            [] => (),
+            &[freq] => self.set_frequency(freq),
+            _ if frequency.len() == frames => {
+                freq_step = frequency.iter()
+                    .map(|freq| freq / self.sample_rate * std::f32::consts::TAU)
+                    .collect();
+                automation.freq_step = &freq_step;
+            }
+            _ => self.set_frequency(mean(frequency)),
+        }
+        match gain {
+            [] => (),
+            &[gain] => self.set_gain(gain),
+            _ if gain.len() == frames => automation.gain = gain,
+            _ => self.set_gain(mean(gain)),
+        }
+        if !variation_rate.is_empty() {
+            self.set_variation_rate(mean(variation_rate));
+        }
+        self.process_automated(left, right, voices, &automation);
+    }
+
+    // Renders the given duration in one go, either interleaved (LRLR...) or
+    // planar (all of left followed by all of right).
+    pub fn render(&mut self, seconds: f32, interleaved: bool) -> Vec<f32> {
+        let frames = (seconds.max(0.0) * self.sample_rate) as usize;
+        let mut left = vec![0.0; frames];
+        let mut right = vec![0.0; frames];
+        self.process(&mut left, &mut right);
+        if interleaved {
+            left.into_iter().zip(right).flat_map(|(l, r)| [l, r]).collect()
+        } else {
+            left.extend(right);
+            left
+        }
+    }
+
+    pub fn get_sample(&mut self, left: &mut [f32], right: &mut [f32]) {
+        let len = left.len();
+        assert!(right.len() == left.len());
+        let mut generator = Generator::new(&self.partials, 3.0 * std::f32::consts::TAU / (len as f32), 0.0);
+        generator.view = self.generator[0].view.clone();
+        generator.generate(left, &mut self.params[0], &Automation::default());
+        let mut generator = Generator::new(&self.partials, 3.0 * std::f32::consts::TAU / (len as f32), 0.0);
+        generator.view = self.generator[1].view.clone();
+        generator.generate(right, &mut self.params[1], &Automation::default());
+    }
+
+    // Buffers of any length are split into chunks of at most SAMPLES, ending
+    // exactly where the next renormalisation or mutation is due.
+    fn process_automated(&mut self, left: &mut [f32], right: &mut [f32], voices: &mut [f32],
+            automation: &Automation) {
+        assert!(left.len() == right.len());
+        let frames = left.len();
+        let with_voices = !voices.is_empty() && !self.voices.is_empty();
+        if with_voices {
+            assert!(voices.len() == frames * self.voices.len());
+        }
+        // Which parameters each channel listens to.
+        let source = [0, if self.stereo == Stereo::Linked { 0 } else { 1 }];
+        let mut start = 0;
+        while start < left.len() {
+            let len = (left.len() - start)
+                .min(SAMPLES)
+                .min((self.fix_interval - self.fix_elapsed) as usize)
+                .min(self.mutation_countdown.unwrap_or(u32::MAX) as usize);
+            let range = start..(start + len);
+            let automation = automation.slice(range.clone());
+            let dt = [self.generator[0].time_step(len), self.generator[1].time_step(len)];
+            let before = [self.params[0].unit.clone(), self.params[1].unit.clone()];
+            // Mutating after before is taken spreads the change over the
+            // chunk that ends where it is due. They would be overwritten by
+            // the morph anyway.
+            if self.transport == Transport::Forward && self.morph.is_none() {
+                self.mutate(len);
+            }
+            for channel in 0..=source[1] {
+                let (params, evolution) = (&mut self.params[channel], self.generator[channel].evolution);
+                match &mut self.morph {
+                    Some(morph) => morph[channel].advance(params, dt[channel], len, evolution),
+                    None => params.evolve(dt[channel], evolution),
+                }
+                if let Some(cp) = &mut self.checkpoint {
+                    cp.position[channel] += dt[channel];
+                }
+            }
+            if let Stereo::Coupled(strength) = self.stereo {
+                self.couple(strength, dt);
+            }
+            let channels = [&mut left[range.clone()], &mut right[range.clone()]];
+            for ((data, generator), channel) in channels.into_iter().zip(&mut self.generator).zip(source) {
+                let after = &self.params[channel].unit;
+                generator.follow(data, &before[channel], after, dt[channel], &automation);
+            }
+            if with_voices {
+                let outputs = voices.chunks_mut(frames).zip(&mut self.generator[2..]).zip(&self.voices);
+                for ((data, generator), &channel) in outputs {
+                    let channel = source[channel];
+                    let after = &self.params[channel].unit;
+                    generator.follow(&mut data[range.clone()], &before[channel], after, dt[channel], &automation);
+                }
+            }
+            if self.morph.as_ref().is_some_and(|[m, _]| m.done()) {
+                self.morph = None;
+            }
+            start += len;
+            self.fix_elapsed += len as u32;
+            if self.fix_elapsed == self.fix_interval {
+                self.params[0].normalize();
+                self.params[1].normalize();
+                for generator in &mut self.generator {
+                    generator.normalize();
+                }
+                self.fix_elapsed = 0;
+            }
+        }
+        self.apply_width(left, right);
+    }
+
+    fn mutate(&mut self, len: usize) {
+        if let Mutation::Drift(rate) = self.mutation {
+            let theta_dt = rate * self.mutation_strength * (len as f32) / self.sample_rate;
+            for params in &mut self.params {
+                for (level, target) in self.level_targets.iter().enumerate().take(params.herm.len()) {
+                    if target.probability > 0.0 {
+                        params.drift(level, self.hamiltonian, theta_dt * target.magnitude, &mut self.rng);
+                    }
+                }
+                // Brownian motion on U(n): the angle grows with sqrt(t).
+                if self.unit_target.probability > 0.0 {
+                    params.kick(self.hamiltonian, self.unit_target.magnitude * theta_dt.sqrt(), &mut self.rng);
+                }
+            }
+            self.desync_checkpoint();
+        }
+        let Some(countdown) = &mut self.mutation_countdown else { return };
+        *countdown -= len as u32;
+        if *countdown == 0 {
+            for params in &mut self.params {
+                for (level, target) in self.level_targets.iter().enumerate().take(params.herm.len()) {
+                    if target.fires(&mut self.rng) {
+                        let amount = (self.mutation_strength * target.magnitude).min(1.0);
+                        params.mutate(level, self.hamiltonian, amount, &mut self.rng);
+                    }
+                }
+                if self.unit_target.fires(&mut self.rng) {
+                    let angle = self.mutation_strength * self.unit_target.magnitude;
+                    params.kick(self.hamiltonian, angle, &mut self.rng);
+                }
+            }
+            self.mutation_countdown = self.mutation.countdown(self.sample_rate, &mut self.rng);
+            self.desync_checkpoint();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(setup: impl FnOnce(&mut Instance)) -> Vec<f32> {
+        let mut inst = Instance::new_with_seed(48000.0, 4).unwrap();
+        setup(&mut inst);
+        inst.render(0.1, true)
+    }
+
+    #[test]
+    fn unsmoothed_controls_take_effect() {
+        let base = render(|inst| inst.set_smoothing_time(0.0));
+        let setters: [fn(&mut Instance); 3] = [
+            |inst| inst.set_frequency(440.0),
+            |inst| inst.set_attenuation(2.0),
+            |inst| inst.set_gain(0.5),
+        ];
+        for set in setters {
+            assert_ne!(base, render(|inst| {
+                inst.set_smoothing_time(0.0);
+                set(inst);
+            }));
+        }
+    }
+
+    // Runs the same quarter second of audio in buffers of the given length.
+    fn render_blocks(block: usize, setup: impl FnOnce(&mut Instance)) -> (Vec<f32>, Instance) {
+        let mut inst = Instance::new_with_seed(48000.0, 4).unwrap();
+        setup(&mut inst);
+        inst.checkpoint();
+        let mut out = vec![0.0; 12000];
+        let mut right = vec![0.0; block];
+        for chunk in out.chunks_mut(block) {
+            inst.process(chunk, &mut right[..chunk.len()]);
+        }
+        (out, inst)
+    }
+
+    #[test]
+    fn evolution_speed_ignores_block_size() {
+        let setup = |inst: &mut Instance| {
+            inst.set_smoothing_time(0.0);
+            inst.set_evolution(Evolution::Rk4);
+            inst.set_variation_rate(1000.0);
+        };
+        let (reference, inst) = render_blocks(128, setup);
+        let position = inst.position().unwrap();
+        assert!((position - MAX_VARIATION_RATE / 4.0).abs() < 0.01, "{position}");
+        for block in [100, 32] {
+            let (out, inst) = render_blocks(block, setup);
+            let pos = inst.position().unwrap();
+            assert!((pos - position).abs() < 0.01, "block {block}: {pos}");
+            let err = out.iter().zip(&reference).map(|(a, b)| (a - b).abs()).fold(0.0, f32::max);
+            assert!(err < 1e-2, "block {block}: {err}");
+        }
+    }
+
+    // Renormalisations and mutations fall on the same samples whatever the
+    // buffer length. Kicks are spread over the chunk they end, which does
+    // depend on it, so the output is only compared without them.
+    #[test]
+    fn output_ignores_buffer_length() {
+        let setup = |mutation| move |inst: &mut Instance| {
+            inst.set_evolution(Evolution::Rk4);
+            inst.set_variation_rate(5.0);
+            inst.set_mutation(mutation, 1.0);
+            inst.set_unit_target(Target { probability: 0.5, magnitude: 0.5 });
+        };
+        let (reference, _) = render_blocks(128, setup(Mutation::Off));
+        let (_, mutated) = render_blocks(128, setup(Mutation::Interval(0.02)));
+        for block in [1000, 100, 37] {
+            let (out, _) = render_blocks(block, setup(Mutation::Off));
+            let err = out.iter().zip(&reference).map(|(a, b)| (a - b).abs()).fold(0.0, f32::max);
+            assert!(err < 1e-3, "block {block}: {err}");
+            let (_, inst) = render_blocks(block, setup(Mutation::Interval(0.02)));
+            assert_eq!(inst.rng, mutated.rng, "block {block}");
+            assert_eq!(inst.mutation_countdown, mutated.mutation_countdown, "block {block}");
+            assert_eq!(inst.fix_elapsed, mutated.fix_elapsed, "block {block}");
+        }
+    }
+
+    // Largest jump between consecutive samples of the left channel.
+    fn max_step(out: &[f32]) -> f32 {
+        let left: Vec<f32> = out.iter().step_by(2).copied().collect();
+        left.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn kicks_are_interpolated() {
+        let setup = |inst: &mut Instance, mutation| {
+            inst.set_smoothing_time(0.0);
+            inst.set_frequency(0.0);
+            inst.set_mutation(mutation, 1.0);
+            inst.set_level_target(0, Target::OFF);
+            inst.set_unit_target(Target { probability: 1.0, magnitude: 2.0 });
+        };
+        // The oscillators stand still, only the coefficients move.
+        let steady = max_step(&render(|inst| setup(inst, Mutation::Off)));
+        let kicked = max_step(&render(|inst| setup(inst, Mutation::Interval(0.01))));
+        assert!(steady < 1e-3, "{steady}");
+        assert!(kicked < 0.02, "{kicked}");
+    }
+
+    #[test]
+    fn observer_switch_is_interpolated() {
+        let mut inst = Instance::new_with_seed(48000.0, 4).unwrap();
+        inst.set_smoothing_time(0.0);
+        inst.set_frequency(0.0);
+        let mut out = inst.render(0.05, true);
+        inst.set_observer(0, &Observer::Row(2));
+        out.extend(inst.render(0.05, true));
+        assert!(max_step(&out) < 0.02, "{}", max_step(&out));
+    }
+
+    #[test]
+    fn odd_automation_lengths_fall_back_to_block_rate() {
+        let run = |frequency: &[f32], gain: &[f32]| {
+            let mut inst = Instance::new_with_seed(48000.0, 4).unwrap();
+            let (mut left, mut right) = (vec![0.0; 128], vec![0.0; 128]);
+            inst.process_with_params(&mut left, &mut right, &mut [], frequency, gain, &[]);
+            left
+        };
+        assert_eq!(run(&[200.0, 300.0, 400.0], &[0.5; 7]), run(&[300.0], &[0.5]));
+    }
+
+    #[test]
+    fn invalid_partials_are_rejected() {
+        for ratio in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            let result = Instance::with_partials(&[1.0, ratio], 48000.0, Some(1));
+            assert!(matches!(result, Err(InstanceError::Partial(_))), "{ratio}");
+        }
+        assert!(matches!(Instance::with_partials(&[], 48000.0, Some(1)), Err(InstanceError::NoPartials)));
+        assert!(Instance::with_partials(&[1.0, 2.5], 48000.0, Some(1)).is_ok());
+    }
+}
@@ -0,0 +1,79 @@
+use nalgebra::Complex;
+
+use crate::{Evolution, Instance, SnapshotError};
+use crate::evolution::{narrow, widen, Mat64};
+use crate::params::{Mat, Params};
+
+// Both end points keep evolving while the sounding state moves from one to
+// the other, so the morph is between two living textures.
+pub(crate) struct Morph {
+    source: Params,
+    target: Params,
+    remaining: u32,
+    duration: u32,
+}
+
+impl Morph {
+    pub(crate) fn advance(&mut self, params: &mut Params, dt: f32, samples: usize, evolution: Evolution) {
+        self.source.evolve(dt, evolution);
+        self.target.evolve(dt, evolution);
+        self.remaining = self.remaining.saturating_sub(samples as u32);
+        if self.remaining == 0 {
+            *params = self.target.clone();
+            return;
+        }
+        let s = 1.0 - self.remaining as f32 / self.duration as f32;
+        *params = blend(&self.source, &self.target, s);
+    }
+
+    pub(crate) fn done(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+// Levels present at one end only fade in or out.
+fn blend(a: &Params, b: &Params, s: f32) -> Params {
+    let depth = a.herm.len().max(b.herm.len());
+    let herm = (0..depth).map(|level| match (a.herm.get(level), b.herm.get(level)) {
+        (Some(x), Some(y)) => x * Complex::from(1.0 - s) + y * Complex::from(s),
+        (Some(x), None) => x * Complex::from(1.0 - s),
+        (None, Some(y)) => y * Complex::from(s),
+        (None, None) => unreachable!(),
+    }).collect();
+    let rates = (0..depth).map(|level| match (a.rates.get(level), b.rates.get(level)) {
+        (Some(x), Some(y)) => x + (y - x) * s,
+        (x, y) => *x.or(y).unwrap(),
+    }).collect();
+    Params { herm, rates, unit: geodesic(&a.unit, &b.unit, s) }
+}
+
+// a exp(s log(a^+ b)), the shortest path on U(n). a^+ b is normal, so its
+// Schur form is diagonal and carries the eigenphases.
+fn geodesic(a: &Mat, b: &Mat, s: f32) -> Mat {
+    let a = widen(a);
+    let (q, t) = (a.adjoint() * widen(b)).schur().unpack();
+    let phases = t.diagonal().map(|z| Complex::new(0.0, z.arg() * s as f64).exp());
+    narrow(&a * &q * Mat64::from_diagonal(&phases) * q.adjoint())
+}
+
+impl Instance {
+    // Moves both channels to the state stored in a snapshot over the given
+    // time. Only the parameters are taken from it, the oscillators and the
+    // random state carry on.
+    pub fn morph(&mut self, data: &[u8], seconds: f32) -> Result<(), SnapshotError> {
+        let target = self.decode(data)?.params;
+        let duration = ((seconds * self.sample_rate) as u32).max(1);
+        let [a, b] = self.params.clone();
+        let [c, d] = target;
+        self.morph = Some([
+            Morph { source: a, target: c, remaining: duration, duration },
+            Morph { source: b, target: d, remaining: duration, duration },
+        ]);
+        self.desync_checkpoint();
+        Ok(())
+    }
+
+    pub fn is_morphing(&self) -> bool {
+        self.morph.is_some()
+    }
+}
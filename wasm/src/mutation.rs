@@ -0,0 +1,115 @@
+use nalgebra::*;
+use rand::Rng;
+
+use crate::Hamiltonian;
+use crate::evolution::expi;
+use crate::params::{Params, fix_herm};
+
+// How herm[0], the top of the chain, is varied over time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mutation {
+    Off,
+    // An event every given number of seconds.
+    Interval(f32),
+    // Events at random times with the given average rate per second.
+    Poisson(f32),
+    // Continuous Ornstein-Uhlenbeck drift, decorrelating at the given rate
+    // per second.
+    Drift(f32),
+}
+
+// Whether a mutation event affects a given part of the state, and by how
+// much. For the chain levels the magnitude scales the portion of the new
+// matrix blended in, for the unitary it is the angle of the random rotation.
+// In drift mode, any nonzero probability enables the target and the
+// magnitude scales its drift rate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Target {
+    pub probability: f32,
+    pub magnitude: f32,
+}
+
+impl Default for Mutation {
+    fn default() -> Mutation {
+        Mutation::Interval(1.0)
+    }
+}
+
+impl Mutation {
+    pub const NAMES: [&str; 4] = ["off", "interval", "poisson", "drift"];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Mutation::Off => "off",
+            Mutation::Interval(_) => "interval",
+            Mutation::Poisson(_) => "poisson",
+            Mutation::Drift(_) => "drift",
+        }
+    }
+
+    pub fn from_name(name: &str, value: f32) -> Option<Mutation> {
+        match name {
+            "off" => Some(Mutation::Off),
+            "interval" => Some(Mutation::Interval(value)),
+            "poisson" => Some(Mutation::Poisson(value)),
+            "drift" => Some(Mutation::Drift(value)),
+            _ => None,
+        }
+    }
+
+    // Samples until the next discrete event, None if there are none.
+    pub(crate) fn countdown(self, sample_rate: f32, rng: &mut impl Rng) -> Option<u32> {
+        let seconds = match self {
+            Mutation::Interval(interval) if interval > 0.0 => interval,
+            Mutation::Poisson(rate) if rate > 0.0 => -(1.0 - rng.random::<f32>()).ln() / rate,
+            _ => return None,
+        };
+        Some(((seconds * sample_rate) as u32).max(1))
+    }
+}
+
+impl Target {
+    pub const OFF: Target = Target { probability: 0.0, magnitude: 0.0 };
+    pub const FULL: Target = Target { probability: 1.0, magnitude: 1.0 };
+
+    // Certain outcomes don't consume randomness.
+    pub(crate) fn fires(self, rng: &mut impl Rng) -> bool {
+        if self.probability >= 1.0 {
+            true
+        } else if self.probability <= 0.0 {
+            false
+        } else {
+            rng.random::<f32>() < self.probability
+        }
+    }
+}
+
+impl Params {
+    // Blends a fresh matrix into a chain level, amount 1 replaces it entirely.
+    pub(crate) fn mutate(&mut self, level: usize, ensemble: Hamiltonian, amount: f32, rng: &mut impl Rng) {
+        let dim = self.unit.nrows();
+        let fresh = ensemble.sample(dim, rng);
+        self.herm[level] = if amount >= 1.0 {
+            fresh
+        } else {
+            fix_herm(&self.herm[level] * Complex::from(1.0 - amount) + fresh * Complex::from(amount))
+        };
+    }
+
+    // Exact OU update over a time step with rate * dt = theta_dt. The result
+    // is renormalised so that the speed of the chain stays the same.
+    pub(crate) fn drift(&mut self, level: usize, ensemble: Hamiltonian, theta_dt: f32, rng: &mut impl Rng) {
+        let dim = self.unit.nrows();
+        let decay = (-theta_dt).exp();
+        let noise = (1.0 - decay * decay).sqrt();
+        let fresh = ensemble.sample(dim, rng);
+        self.herm[level] = fix_herm(&self.herm[level] * Complex::from(decay) + fresh * Complex::from(noise));
+    }
+
+    // Rotates unit by exp(iG angle) for a random normalised Hermitian G.
+    pub(crate) fn kick(&mut self, ensemble: Hamiltonian, angle: f32, rng: &mut impl Rng) {
+        let dim = self.unit.nrows();
+        let g = ensemble.sample(dim, rng);
+        self.unit = expi(&g, angle) * &self.unit;
+    }
+}
@@ -0,0 +1,123 @@
+use nalgebra::{Complex, DVector};
+
+use crate::evolution::{narrow, widen};
+use crate::params::Mat;
+
+// The direction from which the evolving unitary is listened to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Observer {
+    Column(usize),
+    Row(usize),
+    // Any complex vector, normalised on use. Column(k) is the k-th unit vector.
+    Vector(Vec<Complex<f32>>),
+}
+
+impl Default for Observer {
+    fn default() -> Observer {
+        Observer::Column(0)
+    }
+}
+
+// The partial coefficients are unit * vector, or unit^T * vector for rows.
+// After set() the old direction is kept until the next block has started
+// from it, so the switch is interpolated like any other change.
+#[derive(Clone)]
+pub(crate) struct View {
+    transposed: bool,
+    vector: Vec<Complex<f32>>,
+    previous: Option<(bool, Vec<Complex<f32>>)>,
+    motion: Option<Motion>,
+}
+
+// The vector turns under a fixed Hamiltonian, stored diagonalised with its
+// spectrum scaled to [-1, 1].
+#[derive(Clone)]
+struct Motion {
+    basis: Mat,
+    frequencies: Vec<f32>,
+    rate: f32,
+}
+
+impl View {
+    pub(crate) fn new(dim: usize) -> View {
+        let mut view = View { transposed: false, vector: Vec::new(), previous: None, motion: None };
+        view.set(&Observer::default(), dim);
+        view
+    }
+
+    // Out of range indices select the last one; a zero vector leaves the
+    // view unchanged.
+    pub(crate) fn set(&mut self, observer: &Observer, dim: usize) {
+        let unit = |k: usize| {
+            let mut v = vec![Complex::from(0.0); dim];
+            v[k.min(dim - 1)] = 1.0.into();
+            v
+        };
+        let (transposed, mut vector) = match observer {
+            Observer::Column(k) => (false, unit(*k)),
+            Observer::Row(k) => (true, unit(*k)),
+            Observer::Vector(v) => (false, v.clone()),
+        };
+        vector.resize(dim, 0.0.into());
+        let norm = vector.iter().map(|z| z.norm_sqr()).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            if self.previous.is_none() && !self.vector.is_empty() {
+                self.previous = Some((self.transposed, self.vector.clone()));
+            }
+            self.transposed = transposed;
+            self.vector = vector.into_iter().map(|z| z / norm).collect();
+        }
+    }
+
+    pub(crate) fn set_motion(&mut self, hamiltonian: &Mat, rate: f32) {
+        let eigen = widen(hamiltonian).symmetric_eigen();
+        let scale = match eigen.eigenvalues.amax() {
+            0.0 => 1.0,
+            scale => scale,
+        };
+        self.motion = Some(Motion {
+            basis: narrow(eigen.eigenvectors),
+            frequencies: eigen.eigenvalues.iter().map(|l| (l / scale) as f32).collect(),
+            rate,
+        });
+    }
+
+    // Returns false if the view is not moving yet.
+    pub(crate) fn set_motion_rate(&mut self, rate: f32) -> bool {
+        match &mut self.motion {
+            _ if rate == 0.0 => self.motion = None,
+            Some(motion) => motion.rate = rate,
+            None => return false,
+        }
+        true
+    }
+
+    pub(crate) fn advance(&mut self, dt: f32) {
+        let Some(motion) = &self.motion else { return };
+        let mut rotated = motion.basis.ad_mul(&DVector::from_column_slice(&self.vector));
+        for (z, f) in rotated.iter_mut().zip(&motion.frequencies) {
+            *z *= Complex::new(0.0, f * motion.rate * dt).exp();
+        }
+        let v = &motion.basis * rotated;
+        self.vector = v.iter().copied().collect();
+    }
+
+    pub(crate) fn observe(&self, unit: &Mat) -> Vec<Complex<f32>> {
+        project(unit, self.transposed, &self.vector)
+    }
+
+    // The coefficients at the start of a block, from the direction before
+    // the last set() if the switch has not been played yet.
+    pub(crate) fn observe_start(&mut self, unit: &Mat) -> Vec<Complex<f32>> {
+        match self.previous.take() {
+            Some((transposed, vector)) => project(unit, transposed, &vector),
+            None => self.observe(unit),
+        }
+    }
+}
+
+fn project(unit: &Mat, transposed: bool, vector: &[Complex<f32>]) -> Vec<Complex<f32>> {
+    let v = DVector::from_column_slice(vector);
+    let coef = if transposed { unit.tr_mul(&v) } else { unit * v };
+    coef.iter().copied().collect()
+}
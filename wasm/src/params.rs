@@ -0,0 +1,63 @@
+use nalgebra::*;
+use rand::Rng;
+
+use crate::{Hamiltonian, Unitary};
+
+pub(crate) type Mat = DMatrix::<Complex<f32>>;
+
+pub(crate) const ITER: usize = 3;
+pub(crate) const MAX_ITER: usize = 16;
+pub(crate) const MAX_LEVEL_RATE: f32 = 4.0;
+
+#[derive(Clone)]
+pub(crate) struct Params {
+    // herm[0] drives herm[1], ..., the last one drives unit.
+    pub(crate) herm: Vec<Mat>,
+    // Speed of each level when acting as the generator of the next one.
+    pub(crate) rates: Vec<f32>,
+    pub(crate) unit: Mat,
+}
+
+impl Params {
+    pub(crate) fn new(dim: usize, depth: usize, ensemble: (Hamiltonian, Unitary), rng: &mut impl Rng) -> Params {
+        let herm = (0..depth).map(|_| ensemble.0.sample(dim, rng)).collect();
+        let unit = ensemble.1.sample(dim, rng);
+        Params { herm, rates: vec![1.0; depth], unit }
+    }
+
+    // New levels are added at the bottom of the chain, next to unit, and
+    // removed from there as well.
+    pub(crate) fn set_depth(&mut self, depth: usize, ensemble: Hamiltonian, rng: &mut impl Rng) {
+        let dim = self.unit.nrows();
+        self.herm.truncate(depth);
+        while self.herm.len() < depth {
+            self.herm.push(ensemble.sample(dim, rng));
+        }
+        self.rates.resize(depth, 1.0);
+    }
+
+    pub(crate) fn normalize(&mut self) {
+        for mx in &mut self.herm {
+            *mx = fix_herm(mx.clone());
+        }
+        self.unit = fix_unit(self.unit.clone());
+    }
+}
+
+// Hermitian, traceless and of unit Frobenius norm. For a single partial
+// the only traceless matrix is zero, which is returned as is.
+pub(crate) fn fix_herm(mut m: Mat) -> Mat {
+    let dim = m.nrows();
+    m = (&m + m.adjoint()) / Complex::from(2.0);
+    m -= Mat::identity(dim, dim) * m.trace() / Complex::from(dim as f32);
+    let norm = m.norm();
+    if dim > 1 && norm > 0.0 {
+        m /= Complex::from(norm);
+    }
+    m
+}
+
+pub(crate) fn fix_unit(m: Mat) -> Mat {
+    let svd = m.svd_unordered(true, true);
+    svd.u.unwrap() * svd.v_t.unwrap()
+}
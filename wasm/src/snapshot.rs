@@ -0,0 +1,209 @@
+use nalgebra::Complex;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Instance, Mutation};
+use crate::params::{Mat, Params, MAX_ITER, MAX_LEVEL_RATE};
+
+const SNAPSHOT_MAGIC: &[u8; 4] = b"WSCP";
+const SNAPSHOT_VERSION: u8 = 1;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Format,
+    Version(u8),
+    Dimension(usize),
+    Truncated,
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SnapshotError::Format => write!(f, "not a wavescapes snapshot"),
+            SnapshotError::Version(v) => write!(f, "unsupported snapshot version {v}"),
+            SnapshotError::Dimension(d) => write!(f, "snapshot has {d} partials, instance has a different count"),
+            SnapshotError::Truncated => write!(f, "snapshot data truncated"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError { }
+
+struct Writer(Vec<u8>);
+
+struct Reader<'a>(&'a [u8]);
+
+pub(crate) struct Decoded {
+    seed: u64,
+    rng: ChaCha8Rng,
+    fix_elapsed: u32,
+    countdown: u32,
+    pub(crate) params: [Params; 2],
+    cx: [Vec<Complex<f32>>; 2],
+}
+
+impl Writer {
+    fn bytes(&mut self, data: &[u8]) {
+        self.0.extend_from_slice(data);
+    }
+
+    fn u32(&mut self, x: u32) {
+        self.bytes(&x.to_le_bytes());
+    }
+
+    fn u64(&mut self, x: u64) {
+        self.bytes(&x.to_le_bytes());
+    }
+
+    fn f32(&mut self, x: f32) {
+        self.bytes(&x.to_le_bytes());
+    }
+
+    fn complex<'a>(&mut self, values: impl Iterator<Item = &'a Complex<f32>>) {
+        for z in values {
+            self.bytes(&z.re.to_le_bytes());
+            self.bytes(&z.im.to_le_bytes());
+        }
+    }
+}
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        let (head, tail) = self.0.split_first_chunk::<N>().ok_or(SnapshotError::Truncated)?;
+        self.0 = tail;
+        Ok(*head)
+    }
+
+    fn u32(&mut self) -> Result<u32, SnapshotError> {
+        Ok(u32::from_le_bytes(self.bytes()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, SnapshotError> {
+        Ok(u64::from_le_bytes(self.bytes()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, SnapshotError> {
+        Ok(f32::from_le_bytes(self.bytes()?))
+    }
+
+    fn complex<'a>(&mut self, values: impl Iterator<Item = &'a mut Complex<f32>>) -> Result<(), SnapshotError> {
+        for z in values {
+            z.re = f32::from_le_bytes(self.bytes()?);
+            z.im = f32::from_le_bytes(self.bytes()?);
+        }
+        Ok(())
+    }
+}
+
+// Snapshots may come from anywhere, and non-finite or wildly off matrices
+// would never recover (the SVD in fix_unit does not even terminate on NaN).
+// The bounds leave room for the drift of Euler steps between two
+// renormalisations.
+fn plausible(params: &Params) -> bool {
+    let dim = params.unit.nrows();
+    let finite = |m: &Mat| m.iter().all(|z| z.re.is_finite() && z.im.is_finite());
+    params.rates.iter().all(|r| r.abs() <= MAX_LEVEL_RATE)
+        && params.herm.iter().all(|m| finite(m) && m.norm() < 2.0 && (m - m.adjoint()).norm() < 1e-3)
+        && finite(&params.unit)
+        && (params.unit.ad_mul(&params.unit) - Mat::identity(dim, dim)).norm() < (dim as f32).sqrt()
+}
+
+impl Instance {
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut out = Writer(Vec::new());
+        out.bytes(SNAPSHOT_MAGIC);
+        out.bytes(&[SNAPSHOT_VERSION]);
+        out.u32(self.partials.len() as u32);
+        out.u32(self.params[0].herm.len() as u32);
+        out.u64(self.seed);
+        out.bytes(&self.rng.get_seed());
+        out.u64(self.rng.get_stream());
+        out.bytes(&self.rng.get_word_pos().to_le_bytes());
+        out.u32(self.fix_elapsed);
+        out.u32(self.mutation_countdown.unwrap_or(u32::MAX));
+        for (params, generator) in self.params.iter().zip(&self.generator) {
+            for &rate in &params.rates {
+                out.f32(rate);
+            }
+            for mx in &params.herm {
+                out.complex(mx.iter());
+            }
+            out.complex(params.unit.iter());
+            out.complex(generator.cx.iter());
+        }
+        out.0
+    }
+
+    pub(crate) fn decode(&self, data: &[u8]) -> Result<Decoded, SnapshotError> {
+        let mut rd = Reader(data);
+        if &rd.bytes::<4>()? != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::Format);
+        }
+        let [version] = rd.bytes()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::Version(version));
+        }
+        let dim = rd.u32()? as usize;
+        if dim != self.partials.len() {
+            return Err(SnapshotError::Dimension(dim));
+        }
+        let depth = rd.u32()? as usize;
+        if depth == 0 || depth > MAX_ITER {
+            return Err(SnapshotError::Format);
+        }
+        let seed = rd.u64()?;
+        let mut rng = ChaCha8Rng::from_seed(rd.bytes()?);
+        rng.set_stream(rd.u64()?);
+        rng.set_word_pos(u128::from_le_bytes(rd.bytes()?));
+        let fix_elapsed = rd.u32()?.min(self.fix_interval - 1);
+        let countdown = rd.u32()?;
+        let empty = Params {
+            herm: vec![Mat::zeros(dim, dim); depth],
+            rates: vec![0.0; depth],
+            unit: Mat::zeros(dim, dim),
+        };
+        let mut params = [empty.clone(), empty];
+        let mut cx = [self.generator[0].cx.clone(), self.generator[1].cx.clone()];
+        for (params, cx) in params.iter_mut().zip(&mut cx) {
+            for rate in &mut params.rates {
+                *rate = rd.f32()?;
+            }
+            for mx in &mut params.herm {
+                rd.complex(mx.iter_mut())?;
+            }
+            rd.complex(params.unit.iter_mut())?;
+            rd.complex(cx.iter_mut())?;
+        }
+        if !rd.0.is_empty() || !params.iter().all(plausible) {
+            return Err(SnapshotError::Format);
+        }
+        if !cx.iter().flatten().all(|z| (0.5..2.0).contains(&z.norm())) {
+            return Err(SnapshotError::Format);
+        }
+        Ok(Decoded { seed, rng, fix_elapsed, countdown, params, cx })
+    }
+
+    // Everything is read into copies first so that a bad snapshot leaves the
+    // instance untouched.
+    pub fn restore(&mut self, data: &[u8]) -> Result<(), SnapshotError> {
+        let Decoded { seed, mut rng, fix_elapsed, countdown, params, cx } = self.decode(data)?;
+        // The mutation mode is not part of the snapshot, the stored countdown
+        // is only used if the current mode has discrete events at all.
+        let mutation_countdown = match self.mutation {
+            Mutation::Interval(_) | Mutation::Poisson(_) if countdown != 0 && countdown != u32::MAX
+                => Some(countdown),
+            _ => self.mutation.countdown(self.sample_rate, &mut rng),
+        };
+        self.seed = seed;
+        self.rng = rng;
+        self.fix_elapsed = fix_elapsed;
+        self.mutation_countdown = mutation_countdown;
+        self.params = params;
+        for (generator, cx) in self.generator.iter_mut().zip(cx) {
+            generator.cx = cx;
+        }
+        self.morph = None;
+        self.desync_checkpoint();
+        Ok(())
+    }
+}
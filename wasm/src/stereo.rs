@@ -0,0 +1,92 @@
+use crate::{Instance, Observer, Output};
+use crate::evolution::expi;
+
+// How the parameters of the two channels relate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Stereo {
+    // Unrelated evolutions, a random stereo image.
+    #[default]
+    Independent,
+    // One evolution (the left one) heard through two observers.
+    Linked,
+    // Each unitary is also driven by the other channel's Hamiltonian, scaled
+    // by the given strength.
+    Coupled(f32),
+}
+
+impl Stereo {
+    pub const NAMES: [&str; 3] = ["independent", "linked", "coupled"];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Stereo::Independent => "independent",
+            Stereo::Linked => "linked",
+            Stereo::Coupled(_) => "coupled",
+        }
+    }
+
+    // The value is the coupling strength, ignored by the other modes.
+    pub fn from_name(name: &str, value: f32) -> Option<Stereo> {
+        match name {
+            "independent" => Some(Stereo::Independent),
+            "linked" => Some(Stereo::Linked),
+            "coupled" => Some(Stereo::Coupled(value)),
+            _ => None,
+        }
+    }
+}
+
+impl Instance {
+    // On entering linked mode the right channel listens to the second
+    // column, set_observer() can pick another one. The right parameters stand
+    // still while linked.
+    pub fn set_stereo(&mut self, stereo: Stereo) {
+        if stereo == Stereo::Linked && self.stereo != Stereo::Linked {
+            self.set_observer(1, &Observer::Column(1));
+        }
+        self.stereo = stereo;
+    }
+
+    // 90° stereo: linked, with the right channel using the left observer
+    // and playing the imaginary part. Switching it off brings back the
+    // previous stereo mode and right channel settings.
+    pub fn set_quadrature(&mut self, on: bool) {
+        if on && self.quadrature.is_none() {
+            let right = &self.generator[1];
+            self.quadrature = Some((self.stereo, right.view.clone(), right.output));
+            self.stereo = Stereo::Linked;
+            self.generator[1] = self.generator[0].clone();
+            self.generator[1].output = Output::Imaginary;
+        } else if !on && let Some((stereo, view, output)) = self.quadrature.take() {
+            self.stereo = stereo;
+            self.generator[1].view = view;
+            self.generator[1].output = output;
+        }
+    }
+
+    // Mid/side width: 0 is mono, 1 leaves the channels alone, above 1 widens.
+    pub fn set_width(&mut self, width: f32) {
+        self.width.set(width.max(0.0), self.ramp);
+    }
+
+    // Lie-Trotter splitting: after their own evolution, the unitaries are
+    // propagated by the other channel's bottom Hamiltonian.
+    pub(crate) fn couple(&mut self, strength: f32, dt: [f32; 2]) {
+        let [a, b] = &mut self.params;
+        let ua = expi(b.herm.last().unwrap(), strength * dt[0] * b.rates.last().unwrap());
+        let ub = expi(a.herm.last().unwrap(), strength * dt[1] * a.rates.last().unwrap());
+        a.unit = ua * &a.unit;
+        b.unit = ub * &b.unit;
+    }
+
+    pub(crate) fn apply_width(&mut self, left: &mut [f32], right: &mut [f32]) {
+        if !self.width.is_ramping() && self.width.next() == 1.0 {
+            return;
+        }
+        for (l, r) in left.iter_mut().zip(right) {
+            let mid = (*l + *r) / 2.0;
+            let side = (*l - *r) / 2.0 * self.width.next();
+            (*l, *r) = (mid + side, mid - side);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::Instance;
+use crate::params::Params;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Transport {
+    #[default]
+    Forward,
+    // The oscillators keep sounding but the parameters stand still.
+    Freeze,
+    // Evolution runs backwards, retracing the texture.
+    Reverse,
+}
+
+// State of both channels at the time of checkpoint(), and the evolution
+// time each has covered since. A linked right channel stands still, so the
+// two can differ. In sync as long as the current state is the stored one
+// evolved by position, i.e., there was no mutation in between.
+pub(crate) struct Checkpoint {
+    params: [Params; 2],
+    pub(crate) position: [f32; 2],
+    pub(crate) in_sync: bool,
+}
+
+impl Transport {
+    pub const ALL: [Transport; 3] = [
+        Transport::Forward,
+        Transport::Freeze,
+        Transport::Reverse,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Transport::Forward => "forward",
+            Transport::Freeze => "freeze",
+            Transport::Reverse => "reverse",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Transport> {
+        Transport::ALL.into_iter().find(|t| t.name() == name)
+    }
+
+    pub(crate) fn direction(self) -> f32 {
+        match self {
+            Transport::Forward => 1.0,
+            Transport::Freeze => 0.0,
+            Transport::Reverse => -1.0,
+        }
+    }
+}
+
+impl Instance {
+    // Mutations are paused unless going forward, so that reversing really
+    // retraces the path.
+    pub fn set_transport(&mut self, transport: Transport) {
+        self.transport = transport;
+        for generator in &mut self.generator {
+            generator.direction = transport.direction();
+        }
+    }
+
+    pub fn checkpoint(&mut self) {
+        self.checkpoint = Some(Checkpoint {
+            params: self.params.clone(),
+            position: [0.0; 2],
+            in_sync: true,
+        });
+    }
+
+    // Evolution time (seconds times variation rate) since the checkpoint.
+    pub fn position(&self) -> Option<f32> {
+        self.checkpoint.as_ref().map(|cp| cp.position[0])
+    }
+
+    // Moves the state to the given evolution time relative to the last
+    // checkpoint. A running morph is abandoned, it would overwrite the
+    // result. Returns false if there is no checkpoint.
+    pub fn scrub(&mut self, position: f32) -> bool {
+        let Some(cp) = &mut self.checkpoint else { return false };
+        self.morph = None;
+        if !cp.in_sync {
+            self.params = cp.params.clone();
+            cp.position = [0.0; 2];
+        }
+        // Each channel goes from where it is, evolve() takes the whole
+        // distance in one call.
+        for ((params, generator), start) in self.params.iter_mut().zip(&self.generator).zip(&mut cp.position) {
+            params.evolve(position - *start, generator.evolution);
+            *start = position;
+        }
+        cp.in_sync = true;
+        true
+    }
+
+    pub(crate) fn desync_checkpoint(&mut self) {
+        if let Some(cp) = &mut self.checkpoint {
+            cp.in_sync = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Evolution, Instance, Mutation, Stereo};
+
+    fn instance() -> Instance {
+        let mut inst = Instance::new_with_seed(48000.0, 9).unwrap();
+        inst.set_mutation(Mutation::Off, 0.0);
+        inst.set_evolution(Evolution::Rk4);
+        inst.set_variation_rate(20.0);
+        inst.checkpoint();
+        inst
+    }
+
+    fn distance(a: &Instance, b: &Instance) -> f32 {
+        a.params.iter().zip(&b.params).map(|(a, b)| (&a.unit - &b.unit).norm()).fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn scrub_reaches_distant_targets() {
+        let mut played = instance();
+        played.render(0.5, true);
+        let position = played.position().unwrap();
+        let mut scrubbed = instance();
+        for target in [40.0, -10.0, position] {
+            assert!(scrubbed.scrub(target));
+            assert_eq!(scrubbed.position(), Some(target));
+        }
+        let d = distance(&played, &scrubbed);
+        assert!(d < 1e-3, "{d}");
+    }
+
+    #[test]
+    fn linked_channel_catches_up() {
+        let mut played = instance();
+        played.set_stereo(Stereo::Linked);
+        played.render(0.5, true);
+        let position = played.position().unwrap();
+        assert!(played.scrub(position));
+        let mut scrubbed = instance();
+        assert!(scrubbed.scrub(position));
+        assert!(distance(&played, &scrubbed) < 1e-3);
+    }
+
+    #[test]
+    fn scrub_after_mutation_restarts_from_checkpoint() {
+        let mut inst = instance();
+        inst.scrub(10.0);
+        inst.randomize();
+        inst.scrub(10.0);
+        let mut reference = instance();
+        reference.scrub(10.0);
+        assert!(distance(&inst, &reference) < 1e-3);
+    }
+}
@@ -0,0 +1,79 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Tuning {
+    Harmonic,
+    OddHarmonic,
+    Major,
+    MajorOctaves,
+    MajorWide,
+    Fourths,
+    #[default]
+    FourthsWide,
+    BohlenPierce,
+    EqualMajor,
+    EqualMinor,
+    EqualQuartal,
+}
+
+impl Tuning {
+    pub const ALL: [Tuning; 11] = [
+        Tuning::Harmonic,
+        Tuning::OddHarmonic,
+        Tuning::Major,
+        Tuning::MajorOctaves,
+        Tuning::MajorWide,
+        Tuning::Fourths,
+        Tuning::FourthsWide,
+        Tuning::BohlenPierce,
+        Tuning::EqualMajor,
+        Tuning::EqualMinor,
+        Tuning::EqualQuartal,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Tuning::Harmonic => "harmonic",
+            Tuning::OddHarmonic => "odd-harmonic",
+            Tuning::Major => "major",
+            Tuning::MajorOctaves => "major-octaves",
+            Tuning::MajorWide => "major-wide",
+            Tuning::Fourths => "fourths",
+            Tuning::FourthsWide => "fourths-wide",
+            Tuning::BohlenPierce => "bohlen-pierce",
+            Tuning::EqualMajor => "equal-major",
+            Tuning::EqualMinor => "equal-minor",
+            Tuning::EqualQuartal => "equal-quartal",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Tuning> {
+        Tuning::ALL.into_iter().find(|t| t.name() == name)
+    }
+
+    pub fn partials(self) -> Vec<f32> {
+        match self {
+            // 1:2:3:4:5
+            Tuning::Harmonic => vec![1.0, 2.0, 3.0, 4.0, 5.0],
+            // 1:3:5:7:9
+            Tuning::OddHarmonic => vec![1.0, 3.0, 5.0, 7.0, 9.0],
+            // 4:5:6
+            Tuning::Major => vec![1.0, 1.25, 1.5],
+            // 4:5:6:8:10
+            Tuning::MajorOctaves => vec![1.0, 1.25, 1.5, 2.0, 2.5],
+            // 4:5:6:8:10:12
+            Tuning::MajorWide => vec![1.0, 1.25, 1.5, 2.0, 2.5, 3.0],
+            // 3:4:5
+            Tuning::Fourths => vec![1.0, 4./3., 5./3.],
+            // 3:4:5:6:8
+            Tuning::FourthsWide => vec![1.0, 4./3., 5./3., 2.0, 8./3.],
+            // 3:5:7 stacked over two tritaves
+            Tuning::BohlenPierce => vec![1.0, 5./3., 7./3., 3.0, 5.0, 7.0],
+            Tuning::EqualMajor => equal_tempered(&[0, 4, 7, 12, 16, 19]),
+            Tuning::EqualMinor => equal_tempered(&[0, 3, 7, 12, 15, 19]),
+            Tuning::EqualQuartal => equal_tempered(&[0, 5, 10, 15, 20]),
+        }
+    }
+}
+
+fn equal_tempered(steps: &[i32]) -> Vec<f32> {
+    steps.iter().map(|&s| 2.0f32.powf(s as f32 / 12.0)).collect()
+}
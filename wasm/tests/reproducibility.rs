@@ -0,0 +1,61 @@
+use wasm::{Instance, SnapshotError, Tuning};
+
+const SAMPLE_RATE: f32 = 48000.0;
+
+#[test]
+fn same_seed_same_output() {
+    let mut a = Instance::new_with_seed(SAMPLE_RATE, 42).unwrap();
+    let mut b = Instance::new_with_seed(SAMPLE_RATE, 42).unwrap();
+    assert_eq!(a.seed(), b.seed());
+    let out = a.render(2.0, true);
+    assert!(out.iter().any(|&x| x != 0.0));
+    assert_eq!(out, b.render(2.0, true));
+}
+
+#[test]
+fn different_seed_different_output() {
+    let mut a = Instance::new_with_seed(SAMPLE_RATE, 1).unwrap();
+    let mut b = Instance::new_with_seed(SAMPLE_RATE, 2).unwrap();
+    assert_ne!(a.render(0.1, true), b.render(0.1, true));
+}
+
+#[test]
+fn snapshot_continues_identically() {
+    let mut a = Instance::with_tuning(Tuning::Harmonic, SAMPLE_RATE, Some(7)).unwrap();
+    a.render(1.3, true);
+    let snapshot = a.snapshot();
+    let mut b = Instance::with_tuning(Tuning::Harmonic, SAMPLE_RATE, Some(8)).unwrap();
+    b.restore(&snapshot).unwrap();
+    assert_eq!(b.seed(), 7);
+    assert_eq!(a.render(2.0, true), b.render(2.0, true));
+    assert_eq!(a.snapshot(), b.snapshot());
+}
+
+#[test]
+fn restore_rejects_bad_input() {
+    let mut inst = Instance::new_with_seed(SAMPLE_RATE, 3).unwrap();
+    let snapshot = inst.snapshot();
+
+    let truncated = &snapshot[..snapshot.len() - 1];
+    assert!(matches!(inst.restore(truncated), Err(SnapshotError::Truncated)));
+    assert!(matches!(inst.restore(&snapshot[..2]), Err(SnapshotError::Truncated)));
+
+    let mut magic = snapshot.clone();
+    magic[0] ^= 0xff;
+    assert!(matches!(inst.restore(&magic), Err(SnapshotError::Format)));
+
+    let mut version = snapshot.clone();
+    version[4] += 1;
+    assert!(matches!(inst.restore(&version), Err(SnapshotError::Version(_))));
+
+    let mut other = Instance::with_partials(&[1.0, 2.0, 3.0], SAMPLE_RATE, Some(3)).unwrap();
+    assert!(matches!(other.restore(&snapshot), Err(SnapshotError::Dimension(_))));
+
+    // A failed restore leaves the instance untouched.
+    let before = inst.snapshot();
+    let mut nan = snapshot.clone();
+    let len = nan.len();
+    nan[len - 100..].fill(0xff);
+    assert!(matches!(inst.restore(&nan), Err(SnapshotError::Format)));
+    assert_eq!(inst.snapshot(), before);
+}
@@ -1,16 +1,72 @@
 import * as wasm from './wasm/pkg/wasm.js';
 
 class RandomNoiseProcessor extends AudioWorkletProcessor {
+  static get parameterDescriptors() {
+    return [
+      { name: 'frequency', defaultValue: 100, minValue: 0, maxValue: sampleRate / 2 },
+      { name: 'gain', defaultValue: 1, minValue: 0 },
+      { name: 'variationRate', defaultValue: 1, minValue: 0, maxValue: 100, automationRate: 'k-rate' }
+    ];
+  }
+
   constructor() {
     super();
+    this.voices = new Float32Array(0);
     this.port.onmessage = ev => {
       if(ev.data.wasm) {
         wasm.initSync({ module: ev.data.wasm })
-        this.processFn = wasm.process;
-        this.handle = wasm.Instance.new_handle(sampleRate);
-      } else if(this.handle && ev.data.buffers) {
+        this.instance = new wasm.Instance(sampleRate);
+        this.port.postMessage({tunings: wasm.tuning_names()});
+      } else if(this.instance && ev.data.tuning) {
+        // Throws on an unknown tuning, the old instance keeps playing then.
+        const instance = wasm.Instance.with_tuning(ev.data.tuning, sampleRate);
+        this.instance.free();
+        this.instance = instance;
+      } else if(this.instance && ev.data.attenuation !== undefined) {
+        this.instance.set_attenuation(ev.data.attenuation);
+      } else if(this.instance && ev.data.smoothingTime !== undefined) {
+        this.instance.set_smoothing_time(ev.data.smoothingTime);
+      } else if(this.instance && ev.data.snapshot) {
+        this.port.postMessage({snapshot: this.instance.snapshot()});
+      } else if(this.instance && ev.data.restore) {
+        this.instance.restore(ev.data.restore);
+      } else if(this.instance && ev.data.morph) {
+        this.instance.morph(ev.data.morph.snapshot, ev.data.morph.seconds);
+      } else if(this.instance && ev.data.observer) {
+        let observer = ev.data.observer;
+        if(observer.column !== undefined) {
+          this.instance.set_observer_column(observer.output, observer.column);
+        } else if(observer.row !== undefined) {
+          this.instance.set_observer_row(observer.output, observer.row);
+        } else if(observer.re) {
+          this.instance.set_observer_vector(observer.output, observer.re, observer.im);
+        }
+        if(observer.motion !== undefined) {
+          this.instance.set_observer_motion(observer.motion);
+        }
+      } else if(this.instance && ev.data.voices) {
+        // Extra voices go to outputs 1, 2, ... in order.
+        this.instance.clear_voices();
+        for(const voice of ev.data.voices)
+          this.instance.add_voice(voice.channel, voice.column);
+      } else if(this.instance && ev.data.output) {
+        let output = ev.data.output;
+        this.instance.set_output(output.output, output.mode, output.value ?? 0);
+      } else if(this.instance && ev.data.quadrature !== undefined) {
+        this.instance.set_quadrature(ev.data.quadrature);
+      } else if(this.instance && ev.data.stereo) {
+        this.instance.set_stereo(ev.data.stereo.mode, ev.data.stereo.value ?? 0);
+      } else if(this.instance && ev.data.width !== undefined) {
+        this.instance.set_width(ev.data.width);
+      } else if(this.instance && ev.data.transport) {
+        this.instance.set_transport(ev.data.transport);
+      } else if(this.instance && ev.data.checkpoint) {
+        this.instance.checkpoint();
+      } else if(this.instance && ev.data.scrub !== undefined) {
+        this.instance.scrub(ev.data.scrub);
+      } else if(this.instance && ev.data.buffers) {
         let buffers = ev.data.buffers;
-        wasm.get_sample(buffers.left, buffers.right, this.handle);
+        this.instance.get_sample(buffers.left, buffers.right);
         this.port.postMessage({buffers}, [buffers.left.buffer, buffers.right.buffer]);
       }
     }
@@ -18,8 +74,18 @@ class RandomNoiseProcessor extends AudioWorkletProcessor {
 
   process(inputs, outputs, parameters) {
     const output = outputs[0];
-    if(this.handle) {
-      this.processFn(output[0], output[1], this.handle);
+    if(this.instance) {
+      const frames = output[0].length;
+      const count = this.instance.voice_count();
+      if(this.voices.length !== count * frames)
+        this.voices = new Float32Array(count * frames);
+      this.instance.process_with_params(output[0], output[1], this.voices,
+        parameters.frequency, parameters.gain, parameters.variationRate);
+      for(let k = 0; k < count && k + 1 < outputs.length; k++) {
+        const voice = this.voices.subarray(k * frames, (k + 1) * frames);
+        for(const channel of outputs[k + 1])
+          channel.set(voice);
+      }
     }
     return true;
   }